@@ -34,10 +34,26 @@ mod imp {
             obj.set_accels_for_action("win.play", &["space"]);
             obj.set_accels_for_action("win.snapshot", &["<ctrl>s"]);
             obj.set_accels_for_action("win.open-snapshot", &["<ctrl>o"]);
+            obj.set_accels_for_action("win.export-statistics", &["<ctrl><shift>e"]);
             obj.set_accels_for_action("win.toggle-design-mode", &["e"]);
             obj.set_accels_for_action("win.new", &["<ctrl>n"]);
             obj.set_accels_for_action("win.new-empty", &["<ctrl>e"]);
             obj.set_accels_for_action("win.random-seed", &["<ctrl>r"]);
+            obj.set_accels_for_action("win.skip-forward", &["<ctrl>j"]);
+            obj.set_accels_for_action("win.resize", &["<ctrl><shift>r"]);
+            obj.set_accels_for_action("win.toggle-census", &["F9"]);
+            obj.set_accels_for_action("win.toggle-statistics", &["F8"]);
+            obj.set_accels_for_action("win.measure-velocity", &["m"]);
+            obj.set_accels_for_action("win.rotate-clockwise", &["<ctrl>bracketright"]);
+            obj.set_accels_for_action("win.rotate-counterclockwise", &["<ctrl>bracketleft"]);
+            obj.set_accels_for_action("win.rotate-half-turn", &["<ctrl>backslash"]);
+            obj.set_accels_for_action("win.flip-horizontally", &["<ctrl><shift>h"]);
+            obj.set_accels_for_action("win.flip-vertically", &["<ctrl><shift>v"]);
+            obj.set_accels_for_action("win.transpose", &["<ctrl><shift>t"]);
+            obj.set_accels_for_action("win.shift-up", &["<alt>Up"]);
+            obj.set_accels_for_action("win.shift-down", &["<alt>Down"]);
+            obj.set_accels_for_action("win.shift-left", &["<alt>Left"]);
+            obj.set_accels_for_action("win.shift-right", &["<alt>Right"]);
         }
     }
 
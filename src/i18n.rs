@@ -4,6 +4,25 @@ pub fn i18n(format: &str) -> String {
     gettext(format)
 }
 
+/// Marks `format` as translatable without translating it, for
+/// constants translated with `i18n` wherever they are shown
+pub const fn i18n_noop(format: &str) -> &str {
+    format
+}
+
+/// Writes `value` with its digits grouped by thousands, as in 1,204
+pub fn group_digits(value: u64) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
 pub fn translators_list() -> Vec<&'static str> {
     vec![
         "Andrea Coronese (English, Italian)",
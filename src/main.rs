@@ -2,6 +2,7 @@ mod application;
 mod config;
 mod i18n;
 mod models;
+mod search;
 mod services;
 mod widgets;
 mod window;
@@ -15,6 +16,12 @@ use gtk::gio;
 use gtk::prelude::*;
 
 fn main() {
+    // Soups are searched from the command line, without any window
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).is_some_and(|arg| arg == search::SOUP_SEARCH_FLAG) {
+        std::process::exit(search::run_soup_search(&args[2..]));
+    }
+
     // Set up gettext translations
     bindtextdomain(GETTEXT_PACKAGE, LOCALEDIR).expect("Unable to bind the text domain");
     bind_textdomain_codeset(GETTEXT_PACKAGE, "UTF-8")
@@ -0,0 +1,59 @@
+/// The point of a universe that stays in place when it is resized,
+/// rows and columns being added or removed on the other sides
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResizeAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    #[default]
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl ResizeAnchor {
+    /// All the anchors, row by row from the top left one
+    pub const ALL: [ResizeAnchor; 9] = [
+        ResizeAnchor::TopLeft,
+        ResizeAnchor::Top,
+        ResizeAnchor::TopRight,
+        ResizeAnchor::Left,
+        ResizeAnchor::Center,
+        ResizeAnchor::Right,
+        ResizeAnchor::BottomLeft,
+        ResizeAnchor::Bottom,
+        ResizeAnchor::BottomRight,
+    ];
+
+    /// Gets by how many rows and columns cells move when a `rows`x`columns`
+    /// universe is resized to `new_rows`x`new_columns` around this anchor
+    pub fn offset(
+        &self,
+        rows: usize,
+        columns: usize,
+        new_rows: usize,
+        new_columns: usize,
+    ) -> (isize, isize) {
+        let index = ResizeAnchor::ALL
+            .iter()
+            .position(|anchor| anchor == self)
+            .unwrap_or_default();
+
+        // 0 keeps the first line in place, 1 the middle one and 2 the last one
+        let shift = |side: usize, old: usize, new: usize| -> isize {
+            let growth = new as isize - old as isize;
+            match side {
+                0 => 0,
+                1 => growth / 2,
+                _ => growth,
+            }
+        };
+        (
+            shift(index / 3, rows, new_rows),
+            shift(index % 3, columns, new_columns),
+        )
+    }
+}
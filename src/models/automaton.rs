@@ -0,0 +1,162 @@
+use super::{Universe, UniverseNeighbourhood, UniverseSnapshot, Wireworld};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The kinds of automata snapshots can be taken of
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AutomatonKind {
+    /// A `Universe` evolving with a Life-like rule
+    #[default]
+    Life,
+
+    /// A `Wireworld` circuit
+    Wireworld,
+}
+
+/// How cells in a given state are painted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StateColor {
+    /// Not painted at all, leaving the background visible
+    Background,
+
+    /// Painted with the cell color picked by the user
+    Foreground,
+
+    /// Painted with the cell color blended towards the background color,
+    /// from 0 (the cell color) to 1 (the background color)
+    Faded(f32),
+
+    /// Painted with a color of its own, as red, green and blue components from 0 to 1
+    Fixed(f32, f32, f32),
+}
+
+/// A cellular automaton evolving on a grid of `rows`x`columns` cells.
+/// Each cell is in one of `states` numbered states, 0 being the empty one.
+/// Anything the grid widget needs to show, edit and run an automaton goes
+/// through this trait, so that it can host automata other than `Universe`
+pub trait CellularAutomaton: fmt::Debug + Send {
+    /// Gets the number of rows of the grid
+    fn rows(&self) -> usize;
+
+    /// Gets the number of columns of the grid
+    fn columns(&self) -> usize;
+
+    /// Gets the number of states a cell can be in
+    fn states(&self) -> u8;
+
+    /// Gets the state of the cell at `row`x`column`
+    fn state(&self, row: usize, column: usize) -> u8;
+
+    /// Sets the state of the cell at `row`x`column`. States past
+    /// the last one are clamped to it
+    fn set_state(&mut self, row: usize, column: usize, state: u8);
+
+    /// Computes the next generation
+    fn step(&mut self);
+
+    /// Gets the number of generations computed so far
+    fn generation(&self) -> u64;
+
+    /// Gets how cells in `state` are painted
+    fn state_color(&self, state: u8) -> StateColor;
+
+    /// Gets the number of cells that are not empty
+    fn population(&self) -> usize {
+        (0..self.rows())
+            .flat_map(|row| (0..self.columns()).map(move |column| (row, column)))
+            .filter(|(row, column)| self.state(*row, *column) != 0)
+            .count()
+    }
+
+    /// Gets the (top, left, bottom, right) rectangle holding every cell that is not empty
+    fn bounding_box(&self) -> Option<(i64, i64, i64, i64)> {
+        let mut bounding_box: Option<(i64, i64, i64, i64)> = None;
+        for row in 0..self.rows() {
+            for column in 0..self.columns() {
+                if self.state(row, column) != 0 {
+                    let (row, column) = (row as i64, column as i64);
+                    bounding_box = Some(match bounding_box {
+                        Some((top, left, bottom, right)) => (
+                            top.min(row),
+                            left.min(column),
+                            bottom.max(row),
+                            right.max(column),
+                        ),
+                        None => (row, column, row, column),
+                    });
+                }
+            }
+        }
+        bounding_box
+    }
+
+    /// Gets the translated name of `state`, for automata whose states have one
+    fn state_name(&self, _state: u8) -> Option<String> {
+        None
+    }
+
+    /// Gets the neighbourhood cells are laid out for, which gives them their shape
+    fn neighbourhood(&self) -> UniverseNeighbourhood {
+        UniverseNeighbourhood::Moore
+    }
+
+    /// Gets how visible the cell at `row`x`column` still is after dying,
+    /// from 0 (not at all) to 1, for automata fading dead cells out
+    fn corpse_heat(&self, _row: usize, _column: usize) -> f64 {
+        0.0
+    }
+
+    /// Computes the next `generations` generations at once, giving up as soon as
+    /// `cancelled` is set. Returns `false` when it gave up, the automaton being
+    /// left at whatever generation it got to
+    fn skip_forward(&mut self, generations: u64, cancelled: &AtomicBool) -> bool {
+        for _ in 0..generations {
+            if cancelled.load(Ordering::Relaxed) {
+                return false;
+            }
+            self.step();
+        }
+        true
+    }
+
+    /// Takes a snapshot of the automaton, if it can be saved
+    fn snapshot(&self) -> Option<UniverseSnapshot> {
+        None
+    }
+
+    /// Gets this automaton as a Life-like `Universe`, if it is one. Operations
+    /// only universes support, such as editing their rule or their topology,
+    /// reshaping them or looking for objects in them, are reached through it
+    fn as_universe(&self) -> Option<&Universe> {
+        None
+    }
+
+    /// Gets this automaton as a mutable Life-like `Universe`, if it is one
+    fn as_universe_mut(&mut self) -> Option<&mut Universe> {
+        None
+    }
+
+    /// Gets roughly how many bytes the cells of the automaton take up,
+    /// so that the copies kept of it can be held within bounds
+    fn heap_size(&self) -> usize;
+
+    /// Copies this automaton into a new box
+    fn clone_boxed(&self) -> Box<dyn CellularAutomaton>;
+}
+
+impl Clone for Box<dyn CellularAutomaton> {
+    fn clone(&self) -> Self {
+        self.clone_boxed()
+    }
+}
+
+impl From<UniverseSnapshot> for Box<dyn CellularAutomaton> {
+    /// Restores the automaton a snapshot was taken of
+    fn from(snapshot: UniverseSnapshot) -> Self {
+        match snapshot.automaton() {
+            AutomatonKind::Life => Box::new(Universe::from(snapshot)),
+            AutomatonKind::Wireworld => Box::new(Wireworld::from(snapshot)),
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use super::parallel::{for_each_row_band, WorkerPool};
+use super::UniverseTopology;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// The living cells of a universe packed as bits, 64 cells per word.
+/// Every row starts on a new word, and the bits of the last word of a
+/// row past the last column are always zero
+#[derive(Debug, Clone)]
+pub struct BitGrid {
+    rows: usize,
+    columns: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+
+    /// The words `words` were computed from by the last `step`
+    previous: Vec<u64>,
+
+    /// Whether `previous` holds an actual generation, rather than being empty
+    stepped: bool,
+
+    /// Words changed by `set` since the last `step`
+    edited: Vec<usize>,
+
+    /// Whether each word has to be computed by the next `step`
+    active: Vec<bool>,
+
+    /// Rows found past the top and the bottom edges by the next `step`
+    above_top: Vec<u64>,
+    below_bottom: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(rows: usize, columns: usize) -> Self {
+        let words_per_row = columns.div_ceil(WORD_BITS);
+        Self {
+            rows,
+            columns,
+            words_per_row,
+            words: vec![0; rows * words_per_row],
+            previous: vec![0; rows * words_per_row],
+            stepped: false,
+            edited: vec![],
+            active: vec![false; rows * words_per_row],
+            above_top: vec![0; words_per_row],
+            below_bottom: vec![0; words_per_row],
+        }
+    }
+
+    /// Gets roughly how many bytes the words of this grid take up
+    pub fn heap_size(&self) -> usize {
+        let words =
+            self.words.len() + self.previous.len() + self.above_top.len() + self.below_bottom.len();
+        words * std::mem::size_of::<u64>()
+            + self.active.len()
+            + self.edited.len() * std::mem::size_of::<usize>()
+    }
+
+    /// Whether this grid is able to step universes with `topology`
+    pub fn supports(topology: &UniverseTopology) -> bool {
+        !matches!(
+            topology,
+            UniverseTopology::CrossSurface | UniverseTopology::Unbounded
+        )
+    }
+
+    fn word_index(&self, row: usize, column: usize) -> (usize, u64) {
+        (
+            row * self.words_per_row + column / WORD_BITS,
+            1 << (column % WORD_BITS),
+        )
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> bool {
+        let (index, mask) = self.word_index(row, column);
+        self.words[index] & mask != 0
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, alive: bool) {
+        let (index, mask) = self.word_index(row, column);
+        if alive {
+            self.words[index] |= mask;
+        } else {
+            self.words[index] &= !mask;
+        }
+        if self.stepped {
+            self.edited.push(index);
+        }
+    }
+
+    fn row(&self, row: usize) -> &[u64] {
+        &self.words[row * self.words_per_row..(row + 1) * self.words_per_row]
+    }
+
+    /// Copies `row` into `target` with its columns in reverse order
+    fn mirror_row_into(&self, row: usize, target: &mut [u64]) {
+        target.fill(0);
+        for column in 0..self.columns {
+            if self.get(row, column) {
+                let mirrored_column = self.columns - 1 - column;
+                target[mirrored_column / WORD_BITS] |= 1 << (mirrored_column % WORD_BITS);
+            }
+        }
+    }
+
+    /// Finds the words whose cells may change in the next `step`: edited words, and
+    /// those with a word changed by the last `step` among the ones around them.
+    /// Returns `false` before the first `step`, when every word has to be computed
+    fn find_active_words(&self, topology: &UniverseTopology, active: &mut [bool]) -> bool {
+        if !self.stepped {
+            return false;
+        }
+
+        let changed = |index: usize| self.words[index] != self.previous[index];
+        let row_changed =
+            |row: usize| (row * self.words_per_row..(row + 1) * self.words_per_row).any(changed);
+        let (first_row_changed, last_row_changed) = (row_changed(0), row_changed(self.rows - 1));
+        let wraps_columns = topology.wrap(0, -1, self.rows, self.columns).is_some();
+        let wraps_rows = topology.wrap(-1, 0, self.rows, self.columns).is_some();
+
+        for row in 0..self.rows {
+            for word in 0..self.words_per_row {
+                let index = row * self.words_per_row + word;
+                active[index] = (-1..=1).any(|delta_row| {
+                    let around_row = row as isize + delta_row;
+                    if !(0..self.rows as isize).contains(&around_row) {
+                        // Rows past the edges are compared as a whole, since
+                        // they may be mirrored when glued back
+                        return wraps_rows
+                            && if around_row < 0 {
+                                last_row_changed
+                            } else {
+                                first_row_changed
+                            };
+                    }
+
+                    (-1..=1).any(|delta_word| {
+                        let around_word = word as isize + delta_word;
+                        let around_word = if (0..self.words_per_row as isize).contains(&around_word)
+                        {
+                            around_word as usize
+                        } else if wraps_columns {
+                            around_word.rem_euclid(self.words_per_row as isize) as usize
+                        } else {
+                            return false;
+                        };
+                        changed(around_row as usize * self.words_per_row + around_word)
+                    })
+                });
+            }
+        }
+        for index in self.edited.iter() {
+            active[*index] = true;
+        }
+
+        true
+    }
+
+    /// Computes the next generation of a two states rule on the Moore
+    /// neighbourhood. `birth` and `survival` have the n-th bit set when
+    /// n living neighbours make a cell be born or survive.
+    /// Neighbour counts for 64 cells are computed at once, with bitwise adders,
+    /// and bands of rows are computed on the threads of `workers`.
+    /// Words whose surroundings did not change since the last step are not computed again
+    pub fn step(
+        &mut self,
+        birth: u16,
+        survival: u16,
+        topology: &UniverseTopology,
+        workers: &WorkerPool,
+    ) {
+        let mut active = std::mem::take(&mut self.active);
+        let tracks_changes = self.find_active_words(topology, &mut active);
+        let mut next = std::mem::take(&mut self.previous);
+        let words_per_row = self.words_per_row;
+        let wraps_columns = matches!(
+            topology,
+            UniverseTopology::Torus
+                | UniverseTopology::HorizontalCylinder
+                | UniverseTopology::KleinBottle
+        );
+
+        let mut above_top = std::mem::take(&mut self.above_top);
+        let mut below_bottom = std::mem::take(&mut self.below_bottom);
+        match topology {
+            UniverseTopology::Torus | UniverseTopology::VerticalCylinder => {
+                above_top.copy_from_slice(self.row(self.rows - 1));
+                below_bottom.copy_from_slice(self.row(0));
+            }
+            UniverseTopology::KleinBottle => {
+                self.mirror_row_into(self.rows - 1, &mut above_top);
+                self.mirror_row_into(0, &mut below_bottom);
+            }
+            _ => {
+                above_top.fill(0);
+                below_bottom.fill(0);
+            }
+        }
+
+        let last_word_mask = match self.columns % WORD_BITS {
+            0 => u64::MAX,
+            used => (1 << used) - 1,
+        };
+        let last_column_bit = (self.columns - 1) % WORD_BITS;
+
+        // Shifts every cell of a row onto its east (or west) neighbour,
+        // so that the result holds the west (or east) neighbour of every cell
+        let west_of = |row: &[u64], word: usize| -> u64 {
+            let carry = if word > 0 {
+                row[word - 1] >> (WORD_BITS - 1)
+            } else if wraps_columns {
+                (row[words_per_row - 1] >> last_column_bit) & 1
+            } else {
+                0
+            };
+            (row[word] << 1) | carry
+        };
+        let east_of = |row: &[u64], word: usize| -> u64 {
+            let carry = if word + 1 < words_per_row {
+                row[word + 1] << (WORD_BITS - 1)
+            } else if wraps_columns {
+                (row[0] & 1) << last_column_bit
+            } else {
+                0
+            };
+            (row[word] >> 1) | carry
+        };
+
+        for_each_row_band(&mut next, words_per_row, workers, |rows, next| {
+            for row in rows.clone() {
+                let above = if row == 0 {
+                    above_top.as_slice()
+                } else {
+                    self.row(row - 1)
+                };
+                let below = if row + 1 == self.rows {
+                    below_bottom.as_slice()
+                } else {
+                    self.row(row + 1)
+                };
+                let current = self.row(row);
+
+                for word in 0..self.words_per_row {
+                    let index = row * words_per_row + word;
+                    if tracks_changes && !active[index] {
+                        next[(row - rows.start) * words_per_row + word] = current[word];
+                        continue;
+                    }
+
+                    let neighbours = [
+                        west_of(above, word),
+                        above[word],
+                        east_of(above, word),
+                        west_of(current, word),
+                        east_of(current, word),
+                        west_of(below, word),
+                        below[word],
+                        east_of(below, word),
+                    ];
+
+                    let mut next_word = next_word(neighbours, current[word], birth, survival);
+                    if word + 1 == self.words_per_row {
+                        next_word &= last_word_mask;
+                    }
+                    next[(row - rows.start) * words_per_row + word] = next_word;
+                }
+            }
+        });
+
+        self.previous = std::mem::replace(&mut self.words, next);
+        self.active = active;
+        self.above_top = above_top;
+        self.below_bottom = below_bottom;
+        self.stepped = true;
+        self.edited.clear();
+    }
+
+    /// Counts the cells changed by the last `step`
+    pub fn changes_count(&self) -> usize {
+        self.words
+            .iter()
+            .zip(self.previous.iter())
+            .map(|(word, previous)| (word ^ previous).count_ones() as usize)
+            .sum()
+    }
+
+    /// Calls `f` with the row, the column and the new state of every
+    /// cell changed by the last `step`
+    pub fn for_each_change<F: FnMut(usize, usize, bool)>(&self, mut f: F) {
+        for (index, (word, previous)) in self.words.iter().zip(self.previous.iter()).enumerate() {
+            let mut changed = word ^ previous;
+            let (row, first_column) = (
+                index / self.words_per_row,
+                (index % self.words_per_row) * WORD_BITS,
+            );
+            while changed != 0 {
+                let bit = changed.trailing_zeros() as usize;
+                f(row, first_column + bit, word >> bit & 1 == 1);
+                changed &= changed - 1;
+            }
+        }
+    }
+}
+
+/// Computes the next state of 64 cells at once, out of the words holding
+/// their eight neighbours and their current state, for two states rules
+/// on the Moore neighbourhood
+pub(super) fn next_word(neighbours: [u64; 8], alive: u64, birth: u16, survival: u16) -> u64 {
+    // Four bit planes of the neighbours count of each cell
+    let mut count = [0u64; 4];
+    for neighbour in neighbours {
+        let mut carry = neighbour;
+        for bit in count.iter_mut() {
+            let sum = *bit ^ carry;
+            carry &= *bit;
+            *bit = sum;
+        }
+    }
+
+    let mut next_word = 0;
+    for n in 0..=8 {
+        let (born, survives) = (birth >> n & 1 == 1, survival >> n & 1 == 1);
+        if !born && !survives {
+            continue;
+        }
+
+        let matches_count = count
+            .iter()
+            .enumerate()
+            .fold(u64::MAX, |acc, (bit, plane)| {
+                acc & if n >> bit & 1 == 1 { *plane } else { !*plane }
+            });
+        next_word |= matches_count
+            & match (born, survives) {
+                (true, true) => u64::MAX,
+                (true, false) => !alive,
+                _ => alive,
+            };
+    }
+    next_word
+}
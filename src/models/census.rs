@@ -0,0 +1,463 @@
+use super::{Motion, Universe, UniverseRule};
+use std::collections::{HashMap, HashSet};
+
+/// Living cells this many rows or columns apart, or closer, belong to the same object
+const OBJECT_REACH: i64 = 2;
+
+/// Objects with more cells than this are not simulated, and left unidentified
+const CENSUS_MAX_OBJECT_CELLS: usize = 1024;
+
+/// The longest period objects are identified with
+const CENSUS_MAX_PERIOD: u64 = 256;
+
+/// The digits of the extended Wechsler format, as used by apgcodes
+const WECHSLER_DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Coordinates of living cells, as rows and columns
+pub type ObjectCells = Vec<(i64, i64)>;
+
+/// What an object turns out to be, once simulated on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ObjectKind {
+    /// An object that does not change
+    StillLife,
+
+    /// An object going back to the same shape, in the same place, every `period` generations
+    Oscillator { period: u64 },
+
+    /// An object going back to the same shape every `period` generations,
+    /// moved by `displacement` rows and columns
+    Spaceship {
+        period: u64,
+        displacement: (i64, i64),
+    },
+
+    /// An object that does not settle into any of the above, within
+    /// the longest period looked for, or that dies out on its own
+    Unidentified,
+}
+
+/// An object once simulated on its own
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ObjectClassification {
+    kind: ObjectKind,
+
+    /// The apgcode of the object, the name it goes by on Catagolue,
+    /// `None` for unidentified objects
+    code: Option<String>,
+}
+
+impl ObjectClassification {
+    /// Simulates `cells` on their own under `rule`, to find out
+    /// what kind of object they make and its apgcode
+    pub fn of(cells: &[(i64, i64)], rule: &UniverseRule) -> Self {
+        let unidentified = Self {
+            kind: ObjectKind::Unidentified,
+            code: None,
+        };
+        if cells.is_empty() || cells.len() > CENSUS_MAX_OBJECT_CELLS {
+            return unidentified;
+        }
+
+        let mut phases = vec![];
+        let mut current = cells.to_vec();
+        let motion = Motion::trace(
+            cells,
+            CENSUS_MAX_PERIOD,
+            4 * CENSUS_MAX_OBJECT_CELLS,
+            || {
+                current = step_cells(&current, rule);
+                current.clone()
+            },
+            Some(&mut phases),
+        );
+        let Ok(motion) = motion else {
+            return unidentified;
+        };
+
+        let kind = match (motion.period(), motion.displacement()) {
+            (1, (0, 0)) => ObjectKind::StillLife,
+            (period, (0, 0)) => ObjectKind::Oscillator { period },
+            (period, displacement) => ObjectKind::Spaceship {
+                period,
+                displacement,
+            },
+        };
+        let code = apgcode(&kind, &phases);
+        Self {
+            kind,
+            code: Some(code),
+        }
+    }
+
+    pub fn kind(&self) -> ObjectKind {
+        self.kind
+    }
+
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// Gets the common name of the object, for the objects most
+    /// often met in Conway's Game of Life
+    pub fn name(&self, rule: &UniverseRule) -> Option<&'static str> {
+        if *rule != UniverseRule::conway() {
+            return None;
+        }
+
+        let name = match self.code.as_deref()? {
+            "xs4_33" => "Block",
+            "xs6_696" => "Beehive",
+            "xs7_2596" => "Loaf",
+            "xs5_253" => "Boat",
+            "xs6_356" => "Ship",
+            "xs4_252" => "Tub",
+            "xs8_6996" => "Pond",
+            "xs7_25ac" => "Long boat",
+            "xs6_25a4" => "Barge",
+            "xs8_69ic" => "Mango",
+            "xs7_178c" => "Eater 1",
+            "xs6_39c" => "Aircraft carrier",
+            "xs6_bd" => "Snake",
+            "xp2_7" => "Blinker",
+            "xp2_7e" => "Toad",
+            "xp2_318c" => "Beacon",
+            "xp3_co9nas0san9oczgoldlo0oldlogz1047210127401" => "Pulsar",
+            "xp15_4r4z4r4" => "Pentadecathlon",
+            "xq4_153" => "Glider",
+            "xq4_6frc" => "Lightweight spaceship",
+            "xq4_27dee6" => "Middleweight spaceship",
+            "xq4_27deee6" => "Heavyweight spaceship",
+            _ => return None,
+        };
+        Some(name)
+    }
+}
+
+/// A kind of object found in a universe, and how many of them there are.
+/// Spaceships of the same kind moving in different directions are counted together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CensusEntry {
+    classification: ObjectClassification,
+    name: Option<&'static str>,
+    count: usize,
+}
+
+impl CensusEntry {
+    pub fn kind(&self) -> ObjectKind {
+        self.classification.kind()
+    }
+
+    /// Gets the apgcode of the objects, `None` for unidentified ones
+    pub fn code(&self) -> Option<&str> {
+        self.classification.code()
+    }
+
+    /// Gets the common name of the objects, when they have one
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// The objects making up a universe, as a count of each kind of them.
+/// Living cells are split into objects made of the cells close enough
+/// to each other, and each object is simulated on its own to be identified
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Census {
+    /// Kinds of objects, the most common first, unidentified ones last
+    entries: Vec<CensusEntry>,
+}
+
+impl Census {
+    /// Whether objects can be simulated on their own under `rule`: two states
+    /// Life-like rules, without births out of nothing
+    pub fn supports(rule: &UniverseRule) -> bool {
+        rule.is_life_like() && !rule.is_generations() && !rule.is_birth(0)
+    }
+
+    /// Takes the census of the living cells of `universe`. Returns `None` when
+    /// its rule is not supported. Objects crossing the edges of wrapping
+    /// universes are taken as separate pieces
+    pub fn of(universe: &Universe) -> Option<Self> {
+        let rule = universe.rule();
+        if !Self::supports(rule) {
+            return None;
+        }
+
+        // Objects are counted by apgcode, which is the same whatever their orientation
+        let mut classifications: HashMap<ObjectCells, ObjectClassification> = HashMap::new();
+        let mut counts: HashMap<Option<String>, (ObjectClassification, usize)> = HashMap::new();
+        for object in split_objects(&universe.alive_cells()) {
+            let (_, shape) = normalize(&object);
+            let classification = classifications
+                .entry(shape)
+                .or_insert_with(|| ObjectClassification::of(&object, rule));
+            counts
+                .entry(classification.code.clone())
+                .or_insert_with(|| (classification.clone(), 0))
+                .1 += 1;
+        }
+
+        let mut entries: Vec<CensusEntry> = counts
+            .into_values()
+            .map(|(classification, count)| CensusEntry {
+                name: classification.name(rule),
+                classification,
+                count,
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            a.code()
+                .is_none()
+                .cmp(&b.code().is_none())
+                .then_with(|| b.count.cmp(&a.count))
+                .then_with(|| a.code().cmp(&b.code()))
+        });
+
+        Some(Self { entries })
+    }
+
+    pub fn entries(&self) -> &[CensusEntry] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Gets the number of objects counted
+    pub fn objects(&self) -> usize {
+        self.entries.iter().map(CensusEntry::count).sum()
+    }
+}
+
+/// Splits `cells` into objects, each made of the cells within
+/// `OBJECT_REACH` rows and columns of another cell of the object
+pub fn split_objects(cells: &[(i64, i64)]) -> Vec<ObjectCells> {
+    let mut remaining: HashSet<(i64, i64)> = cells.iter().copied().collect();
+    let mut objects = vec![];
+    for &cell in cells {
+        if !remaining.remove(&cell) {
+            continue;
+        }
+
+        let mut object = vec![cell];
+        let mut index = 0;
+        while index < object.len() {
+            let (row, column) = object[index];
+            for d_row in -OBJECT_REACH..=OBJECT_REACH {
+                for d_column in -OBJECT_REACH..=OBJECT_REACH {
+                    let neighbour = (row + d_row, column + d_column);
+                    if remaining.remove(&neighbour) {
+                        object.push(neighbour);
+                    }
+                }
+            }
+            index += 1;
+        }
+        objects.push(object);
+    }
+    objects
+}
+
+/// Computes the next generation of `cells` under `rule`, on a plane with no edges
+pub fn step_cells(cells: &[(i64, i64)], rule: &UniverseRule) -> ObjectCells {
+    let alive: HashSet<(i64, i64)> = cells.iter().copied().collect();
+    let mut neighbours: HashMap<(i64, i64), u16> = HashMap::new();
+    for &(row, column) in cells {
+        for d_row in -1..=1 {
+            for d_column in -1..=1 {
+                if d_row != 0 || d_column != 0 {
+                    *neighbours
+                        .entry((row + d_row, column + d_column))
+                        .or_default() += 1;
+                }
+            }
+        }
+    }
+
+    for &cell in cells {
+        neighbours.entry(cell).or_default();
+    }
+    neighbours
+        .into_iter()
+        .filter(|(cell, count)| {
+            if alive.contains(cell) {
+                rule.is_survival(*count)
+            } else {
+                rule.is_birth(*count)
+            }
+        })
+        .map(|(cell, _)| cell)
+        .collect()
+}
+
+/// Moves `cells` so that their bounding box starts at row and column zero,
+/// and sorts them. Returns where the bounding box started, and the moved cells
+pub(super) fn normalize(cells: &[(i64, i64)]) -> ((i64, i64), ObjectCells) {
+    let top = cells.iter().map(|(row, _)| *row).min().unwrap_or_default();
+    let left = cells
+        .iter()
+        .map(|(_, column)| *column)
+        .min()
+        .unwrap_or_default();
+    let mut shape: ObjectCells = cells
+        .iter()
+        .map(|(row, column)| (row - top, column - left))
+        .collect();
+    shape.sort_unstable();
+    ((top, left), shape)
+}
+
+/// Gets the apgcode of an object of `kind` going through `phases`: its prefix, and
+/// the shortest extended Wechsler encoding among all the phases and orientations,
+/// the first in ASCII order among the shortest ones
+fn apgcode(kind: &ObjectKind, phases: &[ObjectCells]) -> String {
+    let prefix = match kind {
+        ObjectKind::StillLife => format!("xs{}", phases[0].len()),
+        ObjectKind::Oscillator { period } => format!("xp{period}"),
+        ObjectKind::Spaceship { period, .. } => format!("xq{period}"),
+        ObjectKind::Unidentified => String::from("xx"),
+    };
+
+    type Orientation = fn((i64, i64)) -> (i64, i64);
+    let orientations: [Orientation; 8] = [
+        |(row, column)| (row, column),
+        |(row, column)| (row, -column),
+        |(row, column)| (-row, column),
+        |(row, column)| (-row, -column),
+        |(row, column)| (column, row),
+        |(row, column)| (column, -row),
+        |(row, column)| (-column, row),
+        |(row, column)| (-column, -row),
+    ];
+    let encoding = phases
+        .iter()
+        .flat_map(|phase| {
+            orientations.iter().map(move |orient| {
+                let oriented: ObjectCells = phase.iter().map(|cell| orient(*cell)).collect();
+                wechsler(&normalize(&oriented).1)
+            })
+        })
+        .min_by(|a, b| (a.len(), a).cmp(&(b.len(), b)))
+        .unwrap_or_default();
+
+    format!("{prefix}_{encoding}")
+}
+
+/// Encodes normalized `cells` in the extended Wechsler format: strips of five
+/// rows separated by `z`, each column of a strip being a digit, and runs of
+/// zeros written as `w` (two), `x` (three) or `y` followed by a digit (four and more)
+fn wechsler(cells: &[(i64, i64)]) -> String {
+    let height = cells
+        .iter()
+        .map(|(row, _)| *row + 1)
+        .max()
+        .unwrap_or_default() as usize;
+    let width = cells
+        .iter()
+        .map(|(_, column)| *column + 1)
+        .max()
+        .unwrap_or_default() as usize;
+
+    let mut strips = vec![vec![0u8; width]; height.div_ceil(5)];
+    for (row, column) in cells {
+        strips[*row as usize / 5][*column as usize] |= 1 << (row % 5);
+    }
+
+    let encoded: Vec<String> = strips
+        .iter()
+        .map(|strip| {
+            let length = strip
+                .iter()
+                .rposition(|digit| *digit != 0)
+                .map_or(0, |last| last + 1);
+            let mut encoded = String::new();
+            let mut zeros = 0;
+            for digit in &strip[..length] {
+                if *digit == 0 {
+                    zeros += 1;
+                    continue;
+                }
+                push_zeros(&mut encoded, zeros);
+                zeros = 0;
+                encoded.push(WECHSLER_DIGITS[*digit as usize] as char);
+            }
+            encoded
+        })
+        .collect();
+    encoded.join("z")
+}
+
+/// Writes a run of `zeros` zeros in the extended Wechsler format
+fn push_zeros(encoded: &mut String, mut zeros: usize) {
+    while zeros > 0 {
+        match zeros {
+            1 => encoded.push('0'),
+            2 => encoded.push('w'),
+            3 => encoded.push('x'),
+            _ => {
+                let run = zeros.min(39);
+                encoded.push('y');
+                encoded.push(WECHSLER_DIGITS[run - 4] as char);
+                zeros -= run;
+                continue;
+            }
+        }
+        break;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOCK: [(i64, i64); 4] = [(0, 0), (0, 1), (1, 0), (1, 1)];
+    const GLIDER: [(i64, i64); 5] = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+    const LIGHTWEIGHT_SPACESHIP: [(i64, i64); 9] = [
+        (0, 1),
+        (0, 4),
+        (1, 0),
+        (2, 0),
+        (2, 4),
+        (3, 0),
+        (3, 1),
+        (3, 2),
+        (3, 3),
+    ];
+
+    #[test]
+    fn objects_get_their_catagolue_apgcodes() {
+        let conway = UniverseRule::conway();
+        for (cells, code, name) in [
+            (&BLOCK[..], "xs4_33", "Block"),
+            (&GLIDER[..], "xq4_153", "Glider"),
+            (
+                &LIGHTWEIGHT_SPACESHIP[..],
+                "xq4_6frc",
+                "Lightweight spaceship",
+            ),
+        ] {
+            let classification = ObjectClassification::of(cells, &conway);
+            assert_eq!(classification.code(), Some(code));
+            assert_eq!(classification.name(&conway), Some(name));
+        }
+    }
+
+    #[test]
+    fn apgcodes_do_not_depend_on_orientation_or_phase() {
+        let conway = UniverseRule::conway();
+        let mirrored: Vec<_> = GLIDER.iter().map(|(row, column)| (*row, -column)).collect();
+        let next_phase = step_cells(&GLIDER, &conway);
+        for cells in [mirrored, next_phase] {
+            assert_eq!(
+                ObjectClassification::of(&cells, &conway).code(),
+                Some("xq4_153")
+            );
+        }
+    }
+}
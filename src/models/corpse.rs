@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The heat a cell is given when it dies, unless told otherwise
+pub const CORPSE_DEFAULT_INITIAL_HEAT: f64 = 0.65;
+
+/// The heat corpses lose each generation, unless told otherwise
+pub const CORPSE_DEFAULT_DECAY_RATE: f64 = 0.30;
+
+/// Corpses colder than this are not visible anymore, and count as cold
+const CORPSE_COLD_HEAT: f64 = 0.01;
+
+/// How the heat of corpses fades away, generation after generation
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecayCurve {
+    /// Corpses lose the same amount of heat each generation
+    #[default]
+    Linear,
+
+    /// Corpses lose the same share of their heat each generation
+    Exponential,
+
+    /// Corpses cool down as with the linear curve, but are shown in whole
+    /// steps of the initial heat, so that a cell that died once stays
+    /// at its initial heat until it suddenly goes cold
+    Step,
+}
+
+impl DecayCurve {
+    /// All the curves, in the order they are offered to the user
+    pub const ALL: [DecayCurve; 3] = [
+        DecayCurve::Linear,
+        DecayCurve::Exponential,
+        DecayCurve::Step,
+    ];
+}
+
+impl fmt::Display for DecayCurve {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            DecayCurve::Linear => "linear",
+            DecayCurve::Exponential => "exponential",
+            DecayCurve::Step => "step",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownDecayCurveError;
+
+impl fmt::Display for UnknownDecayCurveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown decay curve")
+    }
+}
+
+impl std::error::Error for UnknownDecayCurveError {}
+
+impl FromStr for DecayCurve {
+    type Err = UnknownDecayCurveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DecayCurve::ALL
+            .into_iter()
+            .find(|curve| curve.to_string() == s)
+            .ok_or(UnknownDecayCurveError)
+    }
+}
+
+/// Describes how dead cells fade out. Each death adds `initial_heat`
+/// to the heat of a cell, up to 1, so that cells dying over and over
+/// glow brighter than the ones that died once. Heat then decays
+/// by `rate` each generation, following `curve`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct CorpseDecay {
+    /// The heat each death adds, from 0 to 1
+    initial_heat: f64,
+
+    /// The heat lost each generation from 0 to 1: an amount of
+    /// heat for linear and step curves, a share of it for exponential ones
+    rate: f64,
+
+    curve: DecayCurve,
+}
+
+impl Default for CorpseDecay {
+    fn default() -> Self {
+        Self::new(
+            CORPSE_DEFAULT_INITIAL_HEAT,
+            CORPSE_DEFAULT_DECAY_RATE,
+            DecayCurve::default(),
+        )
+    }
+}
+
+impl CorpseDecay {
+    pub fn new(initial_heat: f64, rate: f64, curve: DecayCurve) -> Self {
+        let mut decay = Self {
+            initial_heat: 0.0,
+            rate: 0.0,
+            curve,
+        };
+        decay.set_initial_heat(initial_heat);
+        decay.set_rate(rate);
+        decay
+    }
+
+    pub fn initial_heat(&self) -> f64 {
+        self.initial_heat
+    }
+
+    pub fn set_initial_heat(&mut self, value: f64) {
+        self.initial_heat = value.clamp(0.0, 1.0);
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn set_rate(&mut self, value: f64) {
+        self.rate = value.clamp(0.0, 1.0);
+    }
+
+    pub fn curve(&self) -> DecayCurve {
+        self.curve
+    }
+
+    /// Gets the heat of a cell dying with `heat` left from its previous deaths
+    pub fn heat_up(&self, heat: f64) -> f64 {
+        (heat + self.initial_heat).min(1.0)
+    }
+
+    /// Gets the heat left to a corpse with `heat` after one more generation.
+    /// Corpses too cold to be seen are cold altogether
+    pub fn cool_down(&self, heat: f64) -> f64 {
+        let heat = match self.curve {
+            DecayCurve::Linear | DecayCurve::Step => heat - self.rate,
+            DecayCurve::Exponential => heat * (1.0 - self.rate),
+        };
+        if heat < CORPSE_COLD_HEAT {
+            0.0
+        } else {
+            heat
+        }
+    }
+
+    /// Gets how visible a corpse with `heat` is, from 0 (not at all) to 1
+    pub fn visibility(&self, heat: f64) -> f64 {
+        match self.curve {
+            DecayCurve::Step if heat > 0.0 && self.initial_heat > 0.0 => {
+                ((heat / self.initial_heat).ceil() * self.initial_heat).min(1.0)
+            }
+            _ => heat,
+        }
+    }
+}
@@ -0,0 +1,439 @@
+use super::UniverseRule;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+type NodeId = u32;
+
+const DEAD: NodeId = 0;
+const ALIVE: NodeId = 1;
+
+/// A square of 2^`level` cells side, made of four squares of the level below.
+/// Level zero nodes are single cells
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    level: u8,
+    nw: NodeId,
+    ne: NodeId,
+    sw: NodeId,
+    se: NodeId,
+    population: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashLifeError {
+    /// Only two states rules on the Moore neighbourhood are supported
+    UnsupportedRule,
+}
+
+impl fmt::Display for HashLifeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HashLifeError::UnsupportedRule => write!(
+                f,
+                "HashLife only supports two states rules on the Moore neighbourhood"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HashLifeError {}
+
+/// A HashLife engine, evolving patterns on an unbounded plane.
+/// The plane is a quadtree whose identical subtrees are stored only once,
+/// and whose futures are memoized, so that huge numbers of generations
+/// of regular patterns can be computed in a handful of steps
+#[derive(Debug, Clone)]
+pub struct HashLife {
+    birth: u16,
+    survival: u16,
+    nodes: Vec<Node>,
+    index: HashMap<[NodeId; 4], NodeId>,
+    results: HashMap<(NodeId, u8), NodeId>,
+    root: NodeId,
+
+    /// Plane coordinates (row, column) of the top left cell of the root
+    origin: (i64, i64),
+    generations: u64,
+}
+
+impl HashLife {
+    /// Whether `rule` can be run by a HashLife engine
+    pub fn supports(rule: &UniverseRule) -> bool {
+        rule.is_life_like() && rule.states() == 2
+    }
+
+    /// Creates an empty plane evolving with `rule`
+    pub fn new(rule: &UniverseRule) -> Result<Self, HashLifeError> {
+        if !Self::supports(rule) {
+            return Err(HashLifeError::UnsupportedRule);
+        }
+
+        let counts_mask = |accepts: &dyn Fn(u16) -> bool| {
+            (0..=8)
+                .filter(|count| accepts(*count))
+                .fold(0u16, |mask, count| mask | 1 << count)
+        };
+
+        let cell = |population| Node {
+            level: 0,
+            nw: DEAD,
+            ne: DEAD,
+            sw: DEAD,
+            se: DEAD,
+            population,
+        };
+
+        let mut engine = Self {
+            birth: counts_mask(&|count| rule.is_birth(count)),
+            survival: counts_mask(&|count| rule.is_survival(count)),
+            nodes: vec![cell(0), cell(1)],
+            index: HashMap::new(),
+            results: HashMap::new(),
+            root: DEAD,
+            origin: (0, 0),
+            generations: 0,
+        };
+        engine.root = engine.empty(3);
+        engine.origin = (-4, -4);
+
+        Ok(engine)
+    }
+
+    /// Gets the number of generations computed so far
+    pub fn generations(&self) -> u64 {
+        self.generations
+    }
+
+    /// Counts the living cells on the whole plane
+    pub fn population(&self) -> u64 {
+        self.nodes[self.root as usize].population
+    }
+
+    fn node(&self, id: NodeId) -> Node {
+        self.nodes[id as usize]
+    }
+
+    fn join(&mut self, nw: NodeId, ne: NodeId, sw: NodeId, se: NodeId) -> NodeId {
+        let children = [nw, ne, sw, se];
+        if let Some(id) = self.index.get(&children) {
+            return *id;
+        }
+
+        let population = children
+            .iter()
+            .map(|child| self.node(*child).population)
+            .sum();
+        let node = Node {
+            level: self.node(nw).level + 1,
+            nw,
+            ne,
+            sw,
+            se,
+            population,
+        };
+        let id = self.nodes.len() as NodeId;
+        self.nodes.push(node);
+        self.index.insert(children, id);
+        id
+    }
+
+    fn empty(&mut self, level: u8) -> NodeId {
+        if level == 0 {
+            return DEAD;
+        }
+
+        let child = self.empty(level - 1);
+        self.join(child, child, child, child)
+    }
+
+    fn side(level: u8) -> i64 {
+        1 << level
+    }
+
+    /// Sets the cell at `row`x`column`, relative to the top left corner of `node`
+    fn with_cell(&mut self, node: NodeId, row: i64, column: i64, alive: bool) -> NodeId {
+        let Node {
+            level,
+            nw,
+            ne,
+            sw,
+            se,
+            ..
+        } = self.node(node);
+        if level == 0 {
+            return if alive { ALIVE } else { DEAD };
+        }
+
+        let half = Self::side(level - 1);
+        match (row < half, column < half) {
+            (true, true) => {
+                let nw = self.with_cell(nw, row, column, alive);
+                self.join(nw, ne, sw, se)
+            }
+            (true, false) => {
+                let ne = self.with_cell(ne, row, column - half, alive);
+                self.join(nw, ne, sw, se)
+            }
+            (false, true) => {
+                let sw = self.with_cell(sw, row - half, column, alive);
+                self.join(nw, ne, sw, se)
+            }
+            (false, false) => {
+                let se = self.with_cell(se, row - half, column - half, alive);
+                self.join(nw, ne, sw, se)
+            }
+        }
+    }
+
+    /// Wraps the root in a node twice as large, keeping it centered
+    fn expand(&mut self) {
+        let root = self.node(self.root);
+        let empty = self.empty(root.level - 1);
+        let nw = self.join(empty, empty, empty, root.nw);
+        let ne = self.join(empty, empty, root.ne, empty);
+        let sw = self.join(empty, root.sw, empty, empty);
+        let se = self.join(root.se, empty, empty, empty);
+        self.root = self.join(nw, ne, sw, se);
+
+        let quarter = Self::side(root.level - 1);
+        self.origin = (self.origin.0 - quarter, self.origin.1 - quarter);
+    }
+
+    /// Sets the cell at plane coordinates `row`x`column`
+    pub fn set(&mut self, row: i64, column: i64, alive: bool) {
+        loop {
+            let side = Self::side(self.node(self.root).level);
+            let (top, left) = self.origin;
+            if (top..top + side).contains(&row) && (left..left + side).contains(&column) {
+                break;
+            }
+            self.expand();
+        }
+
+        self.root = self.with_cell(
+            self.root,
+            row - self.origin.0,
+            column - self.origin.1,
+            alive,
+        );
+    }
+
+    /// Calls `f` with the plane coordinates of every living cell
+    /// in the `rows`x`columns` rectangle with its top left corner at `top`x`left`
+    pub fn for_each_alive_in<F: FnMut(i64, i64)>(
+        &self,
+        top: i64,
+        left: i64,
+        rows: i64,
+        columns: i64,
+        mut f: F,
+    ) {
+        self.visit_alive(
+            self.root,
+            self.origin,
+            (top, left, top + rows, left + columns),
+            &mut f,
+        );
+    }
+
+    /// Calls `f` with the plane coordinates of every living cell
+    pub fn for_each_alive<F: FnMut(i64, i64)>(&self, mut f: F) {
+        let side = Self::side(self.node(self.root).level);
+        let (top, left) = self.origin;
+        self.visit_alive(
+            self.root,
+            self.origin,
+            (top, left, top + side, left + side),
+            &mut f,
+        );
+    }
+
+    fn visit_alive<F: FnMut(i64, i64)>(
+        &self,
+        id: NodeId,
+        corner: (i64, i64),
+        bounds: (i64, i64, i64, i64),
+        f: &mut F,
+    ) {
+        let node = self.node(id);
+        let side = Self::side(node.level);
+        let (top, left, bottom, right) = bounds;
+        if node.population == 0
+            || corner.0 >= bottom
+            || corner.1 >= right
+            || corner.0 + side <= top
+            || corner.1 + side <= left
+        {
+            return;
+        }
+
+        if node.level == 0 {
+            f(corner.0, corner.1);
+            return;
+        }
+
+        let half = Self::side(node.level - 1);
+        self.visit_alive(node.nw, corner, bounds, f);
+        self.visit_alive(node.ne, (corner.0, corner.1 + half), bounds, f);
+        self.visit_alive(node.sw, (corner.0 + half, corner.1), bounds, f);
+        self.visit_alive(node.se, (corner.0 + half, corner.1 + half), bounds, f);
+    }
+
+    /// The level `level - 1` node at the center of a node
+    fn centered(&mut self, id: NodeId) -> NodeId {
+        let node = self.node(id);
+        let (nw, ne, sw, se) = (
+            self.node(node.nw),
+            self.node(node.ne),
+            self.node(node.sw),
+            self.node(node.se),
+        );
+        self.join(nw.se, ne.sw, sw.ne, se.nw)
+    }
+
+    /// Computes one generation of the 2x2 center of a 4x4 node
+    fn step_level_two(&mut self, id: NodeId) -> NodeId {
+        let mut cells = [[false; 4]; 4];
+        for (row, line) in cells.iter_mut().enumerate() {
+            for (column, cell) in line.iter_mut().enumerate() {
+                *cell = self.cell_in(id, row, column);
+            }
+        }
+
+        let mut next = [DEAD; 4];
+        for (index, (row, column)) in [(1, 1), (1, 2), (2, 1), (2, 2)].into_iter().enumerate() {
+            // The 3x3 block around the cell, less the cell itself
+            let neighbours = cells[row - 1..=row + 1]
+                .iter()
+                .flat_map(|line| &line[column - 1..=column + 1])
+                .filter(|alive| **alive)
+                .count()
+                - usize::from(cells[row][column]);
+
+            let accepts = if cells[row][column] {
+                self.survival
+            } else {
+                self.birth
+            };
+            if accepts >> neighbours & 1 == 1 {
+                next[index] = ALIVE;
+            }
+        }
+
+        self.join(next[0], next[1], next[2], next[3])
+    }
+
+    fn cell_in(&self, id: NodeId, row: usize, column: usize) -> bool {
+        let mut node = self.node(id);
+        let (mut row, mut column) = (row, column);
+        while node.level > 0 {
+            let half = 1 << (node.level - 1);
+            let child = match (row < half, column < half) {
+                (true, true) => node.nw,
+                (true, false) => node.ne,
+                (false, true) => node.sw,
+                (false, false) => node.se,
+            };
+            row %= half;
+            column %= half;
+            node = self.node(child);
+        }
+        node.population > 0
+    }
+
+    /// Computes the level `level - 1` node at the center of `id`,
+    /// 2^`jump` generations ahead. `jump` must not exceed `level - 2`
+    fn successor(&mut self, id: NodeId, jump: u8) -> NodeId {
+        let node = self.node(id);
+        if node.population == 0 {
+            return self.empty(node.level - 1);
+        }
+        if let Some(result) = self.results.get(&(id, jump)) {
+            return *result;
+        }
+
+        let result = if node.level == 2 {
+            self.step_level_two(id)
+        } else {
+            let (nw, ne, sw, se) = (
+                self.node(node.nw),
+                self.node(node.ne),
+                self.node(node.sw),
+                self.node(node.se),
+            );
+
+            // Nine overlapping nodes, one level below
+            let n01 = self.join(nw.ne, ne.nw, nw.se, ne.sw);
+            let n10 = self.join(nw.sw, nw.se, sw.nw, sw.ne);
+            let n11 = self.join(nw.se, ne.sw, sw.ne, se.nw);
+            let n12 = self.join(ne.sw, ne.se, se.nw, se.ne);
+            let n21 = self.join(sw.ne, se.nw, sw.se, se.sw);
+            let nine = [node.nw, n01, node.ne, n10, n11, n12, node.sw, n21, node.se];
+
+            // At full speed both halves of the jump are computed recursively,
+            // otherwise the first half only takes the centers
+            let full_speed = jump == node.level - 2;
+            let mut stepped = [DEAD; 9];
+            for (index, sub) in nine.into_iter().enumerate() {
+                stepped[index] = if full_speed {
+                    self.successor(sub, jump - 1)
+                } else {
+                    self.centered(sub)
+                };
+            }
+
+            let second_jump = if full_speed { jump - 1 } else { jump };
+            let quadrants = [
+                self.join(stepped[0], stepped[1], stepped[3], stepped[4]),
+                self.join(stepped[1], stepped[2], stepped[4], stepped[5]),
+                self.join(stepped[3], stepped[4], stepped[6], stepped[7]),
+                self.join(stepped[4], stepped[5], stepped[7], stepped[8]),
+            ];
+            let mut next = [DEAD; 4];
+            for (index, quadrant) in quadrants.into_iter().enumerate() {
+                next[index] = self.successor(quadrant, second_jump);
+            }
+            self.join(next[0], next[1], next[2], next[3])
+        };
+
+        self.results.insert((id, jump), result);
+        result
+    }
+
+    /// Whether the living cells all lie in the central quarter of the root
+    fn is_padded(&mut self) -> bool {
+        let center = self.centered(self.root);
+        let inner = self.centered(center);
+        self.node(inner).population == self.population()
+    }
+
+    /// Computes 2^`jump` generations at once
+    fn advance_power_of_two(&mut self, jump: u8) {
+        while self.node(self.root).level < jump + 3 || !self.is_padded() {
+            self.expand();
+        }
+
+        let level = self.node(self.root).level;
+        self.root = self.successor(self.root, jump);
+
+        let quarter = Self::side(level - 2);
+        self.origin = (self.origin.0 + quarter, self.origin.1 + quarter);
+        self.generations += 1 << jump;
+    }
+
+    /// Computes the next `generations` generations, giving up between two
+    /// of its steps as soon as `cancelled` is set. Returns `false` when it
+    /// gave up, `generations()` telling how far it got
+    pub fn advance(&mut self, generations: u64, cancelled: &AtomicBool) -> bool {
+        for jump in 0..u64::BITS as u8 {
+            if generations >> jump & 1 == 1 {
+                if cancelled.load(Ordering::Relaxed) {
+                    return false;
+                }
+                self.advance_power_of_two(jump);
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,251 @@
+use super::{CellularAutomaton, UniversePoint};
+use std::collections::VecDeque;
+
+/// The number of generations history keeps, unless told otherwise
+pub const HISTORY_DEFAULT_DEPTH: usize = 500;
+
+/// History keeps a whole copy of the automaton at least every this many generations
+const KEYFRAME_INTERVAL: usize = 50;
+
+/// The number of bytes the generations recorded take up at most, the oldest
+/// ones being forgotten past it however deep history is. Automata recorded
+/// as whole copies, as large universes can be, fill it well before their depth
+const HISTORY_MAX_BYTES: usize = 256 * 1024 * 1024;
+
+/// What brings an automaton back to a past generation
+#[derive(Debug, Clone)]
+enum HistoryFrame {
+    /// A whole copy of the automaton as it was
+    Keyframe(Box<dyn CellularAutomaton>),
+
+    /// The points to set on the universe that followed,
+    /// as given by `Universe::reverse_delta`
+    Delta(Vec<UniversePoint>),
+}
+
+impl HistoryFrame {
+    /// Gets roughly how many bytes this frame takes up
+    fn heap_size(&self) -> usize {
+        match self {
+            HistoryFrame::Keyframe(automaton) => automaton.heap_size(),
+            HistoryFrame::Delta(delta) => delta.len() * std::mem::size_of::<UniversePoint>(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct HistoryEntry {
+    generation: u64,
+    population: usize,
+    frame: HistoryFrame,
+}
+
+/// A bounded record of the past generations of an automaton, so that it
+/// can be brought back to them. Life-like universes are recorded as the
+/// few points changing from one generation to the next, with a whole copy
+/// of the universe every now and then; anything else is copied whole.
+/// Either way, generations are forgotten past `HISTORY_MAX_BYTES`.
+///
+/// Recorded generations make up a timeline, from the oldest one at position
+/// zero to the most recent one at position `len`. Going back in time keeps
+/// the generations after the one shown, until a new one is recorded from it
+#[derive(Debug, Clone)]
+pub struct UniverseHistory {
+    /// The number of generations kept, the oldest ones being forgotten first
+    depth: usize,
+
+    /// Recorded generations, the most recent last. Each entry is
+    /// meant to be applied to the one following it, the last entry
+    /// to the most recent generation
+    entries: VecDeque<HistoryEntry>,
+
+    /// Bytes taken up by the frames of `entries`
+    bytes: usize,
+
+    /// Entries recorded since the last keyframe
+    since_keyframe: usize,
+
+    /// The most recent generation, kept aside while an older one is shown
+    head: Option<Box<dyn CellularAutomaton>>,
+
+    /// The position of the generation shown, `None` for the most recent one
+    cursor: Option<usize>,
+}
+
+impl Default for UniverseHistory {
+    fn default() -> Self {
+        Self::new(HISTORY_DEFAULT_DEPTH)
+    }
+}
+
+impl UniverseHistory {
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth,
+            entries: VecDeque::new(),
+            bytes: 0,
+            since_keyframe: 0,
+            head: None,
+            cursor: None,
+        }
+    }
+
+    /// Sets the number of generations kept, forgetting the oldest ones
+    /// past it. Zero keeps no history at all. Generations more recent
+    /// than the one shown are forgotten as well
+    pub fn set_depth(&mut self, value: usize) {
+        self.depth = value;
+        self.fork();
+        self.trim();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Gets the number of past generations recorded, which
+    /// is also the position of the most recent generation
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Gets the position of the generation shown
+    pub fn position(&self) -> usize {
+        self.cursor.unwrap_or(self.entries.len())
+    }
+
+    /// Forgets every generation recorded
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.bytes = 0;
+        self.since_keyframe = 0;
+        self.head = None;
+        self.cursor = None;
+    }
+
+    /// Gets the generation and the population at each position
+    /// of the timeline, `current` being the automaton shown
+    pub fn timeline(&self, current: &dyn CellularAutomaton) -> Vec<(u64, usize)> {
+        let head = self.head.as_deref().unwrap_or(current);
+        self.entries
+            .iter()
+            .map(|entry| (entry.generation, entry.population))
+            .chain(std::iter::once((head.generation(), head.population())))
+            .collect()
+    }
+
+    /// Records `previous`, the automaton `current` was computed from.
+    /// When `previous` is an older generation brought back, the ones
+    /// that followed it are forgotten, `current` taking their place
+    pub fn record(
+        &mut self,
+        previous: Box<dyn CellularAutomaton>,
+        current: &dyn CellularAutomaton,
+    ) {
+        self.fork();
+        if self.depth == 0 {
+            return;
+        }
+
+        let delta = match (previous.as_universe(), current.as_universe()) {
+            (Some(previous), Some(current)) if self.since_keyframe + 1 < KEYFRAME_INTERVAL => {
+                previous.reverse_delta(current)
+            }
+            _ => None,
+        };
+        let (generation, population) = (previous.generation(), previous.population());
+        let frame = match delta {
+            Some(delta) => {
+                self.since_keyframe += 1;
+                HistoryFrame::Delta(delta)
+            }
+            None => {
+                self.since_keyframe = 0;
+                HistoryFrame::Keyframe(previous)
+            }
+        };
+
+        self.bytes += frame.heap_size();
+        self.entries.push_back(HistoryEntry {
+            generation,
+            population,
+            frame,
+        });
+        self.trim();
+    }
+
+    /// Turns `automaton`, the generation shown, into the one at `position`.
+    /// Returns `false`, leaving `automaton` untouched, when that is
+    /// the generation shown already or no generation is there
+    pub fn seek(&mut self, automaton: &mut Box<dyn CellularAutomaton>, position: usize) -> bool {
+        let len = self.entries.len();
+        if position > len || position == self.position() {
+            return false;
+        }
+
+        if position == len {
+            if let Some(head) = self.head.take() {
+                *automaton = head;
+            }
+            self.cursor = None;
+            return true;
+        }
+
+        // Starts from the first keyframe from there on, sparing the deltas
+        // recorded after it, or else from the most recent generation
+        let keyframe = (position..len).find_map(|index| match &self.entries[index].frame {
+            HistoryFrame::Keyframe(keyframe) => Some((keyframe, index)),
+            HistoryFrame::Delta(_) => None,
+        });
+        let (mut target, from) = match keyframe {
+            Some((keyframe, index)) => (keyframe.clone(), index),
+            None => (self.head.as_ref().unwrap_or(automaton).clone(), len),
+        };
+
+        for index in (position..from).rev() {
+            if let (HistoryFrame::Delta(delta), Some(universe)) =
+                (&self.entries[index].frame, target.as_universe_mut())
+            {
+                universe.step_back(delta);
+            }
+        }
+
+        let shown = std::mem::replace(automaton, target);
+        if self.cursor.is_none() {
+            self.head = Some(shown);
+        }
+        self.cursor = Some(position);
+        true
+    }
+
+    /// Forgets the generations more recent than the one shown,
+    /// which becomes the most recent one
+    fn fork(&mut self) {
+        if let Some(cursor) = self.cursor.take() {
+            self.entries.truncate(cursor);
+            self.bytes = self
+                .entries
+                .iter()
+                .map(|entry| entry.frame.heap_size())
+                .sum();
+            self.head = None;
+            self.since_keyframe = self
+                .entries
+                .iter()
+                .rev()
+                .take_while(|entry| matches!(entry.frame, HistoryFrame::Delta(_)))
+                .count();
+        }
+    }
+
+    /// Forgets the oldest generations past the depth of this
+    /// history, or past the bytes it can take up
+    fn trim(&mut self) {
+        while self.entries.len() > self.depth || self.bytes > HISTORY_MAX_BYTES {
+            let Some(entry) = self.entries.pop_front() else {
+                break;
+            };
+            self.bytes -= entry.frame.heap_size();
+        }
+    }
+}
@@ -1,5 +1,37 @@
+mod anchor;
+mod automaton;
+mod bitgrid;
+mod census;
+mod corpse;
+mod hashlife;
+mod history;
+mod motion;
+mod parallel;
 mod prelude;
+mod rule;
+mod soup;
+mod soup_search;
+mod sparse;
+mod stabilization;
+mod statistics;
+mod topology;
+mod transform;
 mod universe;
+mod wireworld;
 
+pub use anchor::*;
+pub use automaton::*;
+pub use census::*;
+pub use corpse::*;
+pub use history::*;
+pub use motion::*;
 pub use prelude::*;
+pub use rule::*;
+pub use soup::*;
+pub use soup_search::*;
+pub use stabilization::*;
+pub use statistics::*;
+pub use topology::*;
+pub use transform::*;
 pub use universe::*;
+pub use wireworld::*;
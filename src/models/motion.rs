@@ -0,0 +1,271 @@
+use super::census::normalize;
+use super::{step_cells, Census, ObjectCells, Universe, UniverseRule};
+use std::fmt;
+
+/// The longest period motion is looked for over
+pub const MOTION_MAX_PERIOD: u64 = 512;
+
+/// Objects growing past this many cells are not followed any further
+const MOTION_MAX_OBJECT_CELLS: usize = 65_536;
+
+/// The direction a pattern moves in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MotionDirection {
+    /// Along the rows or along the columns
+    Orthogonal,
+
+    /// As many rows as columns
+    Diagonal,
+
+    /// Any other direction, as knightships do
+    Oblique,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MotionError {
+    /// Only two states Life-like rules, without births out of nothing, are supported
+    UnsupportedRule,
+
+    /// Every cell died before the pattern went back to its shape
+    DiedOut,
+
+    /// The pattern did not go back to its shape within the longest period looked for
+    NoPeriod,
+}
+
+impl fmt::Display for MotionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MotionError::UnsupportedRule => write!(
+                f,
+                "Motion can only be detected with two states Life-like rules"
+            ),
+            MotionError::DiedOut => write!(f, "The pattern died out"),
+            MotionError::NoPeriod => write!(f, "The pattern did not repeat"),
+        }
+    }
+}
+
+impl std::error::Error for MotionError {}
+
+/// How a pattern repeats: the number of generations it takes to go back
+/// to the same shape, and the rows and columns it moved by meanwhile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Motion {
+    period: u64,
+    displacement: (i64, i64),
+}
+
+impl Motion {
+    pub fn new(period: u64, displacement: (i64, i64)) -> Self {
+        Self {
+            period,
+            displacement,
+        }
+    }
+
+    /// Ticks a copy of `universe` until its living cells go back to the same
+    /// shape, wherever it is. Patterns crossing the edges of wrapping universes
+    /// only repeat once they are back to the same place
+    pub fn of_universe(universe: &Universe) -> Result<Self, MotionError> {
+        if !Census::supports(universe.rule()) {
+            return Err(MotionError::UnsupportedRule);
+        }
+
+        let mut universe = universe.clone();
+        Self::trace(
+            &universe.alive_cells(),
+            MOTION_MAX_PERIOD,
+            usize::MAX,
+            || {
+                universe.tick();
+                universe.alive_cells()
+            },
+            None,
+        )
+    }
+
+    /// Follows `cells` on their own, on a plane with no edges, under `rule`
+    /// until they go back to the same shape
+    pub fn of_object(cells: &[(i64, i64)], rule: &UniverseRule) -> Result<Self, MotionError> {
+        if !Census::supports(rule) {
+            return Err(MotionError::UnsupportedRule);
+        }
+
+        let mut current = cells.to_vec();
+        Self::trace(
+            cells,
+            MOTION_MAX_PERIOD,
+            MOTION_MAX_OBJECT_CELLS,
+            || {
+                current = step_cells(&current, rule);
+                current.clone()
+            },
+            None,
+        )
+    }
+
+    /// Follows `cells` through the generations `next` computes, one at each call,
+    /// until they go back to the same shape within `max_period` generations and
+    /// without growing past `max_cells` cells. The normalized shapes of the
+    /// generations of the period are pushed to `phases`, when given
+    pub(super) fn trace<F>(
+        cells: &[(i64, i64)],
+        max_period: u64,
+        max_cells: usize,
+        mut next: F,
+        mut phases: Option<&mut Vec<ObjectCells>>,
+    ) -> Result<Self, MotionError>
+    where
+        F: FnMut() -> ObjectCells,
+    {
+        if cells.is_empty() {
+            return Err(MotionError::DiedOut);
+        }
+
+        let (origin, shape) = normalize(cells);
+        if let Some(phases) = phases.as_mut() {
+            phases.push(shape.clone());
+        }
+
+        for generation in 1..=max_period {
+            let current = next();
+            if current.is_empty() {
+                return Err(MotionError::DiedOut);
+            }
+            if current.len() > max_cells {
+                return Err(MotionError::NoPeriod);
+            }
+
+            // Quick check on the population before comparing shapes
+            if current.len() == shape.len() {
+                let (next_origin, next_shape) = normalize(&current);
+                if next_shape == shape {
+                    return Ok(Self::new(
+                        generation,
+                        (next_origin.0 - origin.0, next_origin.1 - origin.1),
+                    ));
+                }
+                if let Some(phases) = phases.as_mut() {
+                    phases.push(next_shape);
+                }
+            } else if let Some(phases) = phases.as_mut() {
+                phases.push(normalize(&current).1);
+            }
+        }
+
+        Err(MotionError::NoPeriod)
+    }
+
+    pub fn period(&self) -> u64 {
+        self.period
+    }
+
+    /// Gets the rows and columns the pattern moved by over a period
+    pub fn displacement(&self) -> (i64, i64) {
+        self.displacement
+    }
+
+    pub fn is_moving(&self) -> bool {
+        self.displacement != (0, 0)
+    }
+
+    /// Gets the direction of the pattern, `None` when it does not move
+    pub fn direction(&self) -> Option<MotionDirection> {
+        let (rows, columns) = (
+            self.displacement.0.unsigned_abs(),
+            self.displacement.1.unsigned_abs(),
+        );
+        match (rows, columns) {
+            (0, 0) => None,
+            (0, _) | (_, 0) => Some(MotionDirection::Orthogonal),
+            (rows, columns) if rows == columns => Some(MotionDirection::Diagonal),
+            _ => Some(MotionDirection::Oblique),
+        }
+    }
+
+    /// Writes the speed of the pattern in c notation, c being one cell
+    /// per generation: `c/4` for one cell every four generations, `2c/5` for
+    /// two cells every five. Oblique speeds give the rows and columns moved
+    /// by, as in `(2,1)c/6`. `None` when the pattern does not move
+    pub fn speed(&self) -> Option<String> {
+        let (rows, columns) = (
+            self.displacement.0.unsigned_abs(),
+            self.displacement.1.unsigned_abs(),
+        );
+        if self.direction()? == MotionDirection::Oblique {
+            return Some(format!(
+                "({},{})c/{}",
+                rows.max(columns),
+                rows.min(columns),
+                self.period
+            ));
+        }
+
+        let distance = rows.max(columns);
+        let divisor = gcd(distance, self.period);
+        let speed = match (distance / divisor, self.period / divisor) {
+            (1, 1) => String::from("c"),
+            (cells, 1) => format!("{cells}c"),
+            (1, generations) => format!("c/{generations}"),
+            (cells, generations) => format!("{cells}c/{generations}"),
+        };
+        Some(speed)
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spaceships_get_their_speed() {
+        let conway = UniverseRule::conway();
+        let glider = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+        let lightweight_spaceship = [
+            (0, 1),
+            (0, 4),
+            (1, 0),
+            (2, 0),
+            (2, 4),
+            (3, 0),
+            (3, 1),
+            (3, 2),
+            (3, 3),
+        ];
+        for (cells, direction, speed) in [
+            (&glider[..], MotionDirection::Diagonal, "c/4"),
+            (
+                &lightweight_spaceship[..],
+                MotionDirection::Orthogonal,
+                "c/2",
+            ),
+        ] {
+            let motion = Motion::of_object(cells, &conway).unwrap();
+            assert_eq!(motion.period(), 4);
+            assert_eq!(motion.direction(), Some(direction));
+            assert_eq!(motion.speed().as_deref(), Some(speed));
+        }
+    }
+
+    #[test]
+    fn speeds_are_reduced_fractions() {
+        for (period, displacement, speed) in [
+            (1, (0, 1), Some("c")),
+            (4, (0, 2), Some("c/2")),
+            (6, (0, 4), Some("2c/3")),
+            (6, (2, 1), Some("(2,1)c/6")),
+            (2, (0, 0), None),
+        ] {
+            assert_eq!(Motion::new(period, displacement).speed().as_deref(), speed);
+        }
+    }
+}
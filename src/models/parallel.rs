@@ -0,0 +1,91 @@
+use rayon::prelude::*;
+use std::ops::Range;
+use std::sync::{Arc, OnceLock};
+
+/// Bands thinner than this are not worth a thread of their own
+pub(super) const MIN_ROWS_PER_BAND: usize = 32;
+
+/// Resolves the number of worker threads to use, where zero
+/// means one for each available core
+pub fn resolve_worker_threads(requested: usize) -> usize {
+    match requested {
+        0 => std::thread::available_parallelism()
+            .map(|threads| threads.get())
+            .unwrap_or(1),
+        requested => requested,
+    }
+}
+
+/// The threads computing generations, started the first time they are
+/// needed and kept from one generation to the next. Copies share the
+/// same threads, so that copying an automaton does not start new ones
+#[derive(Debug, Clone, Default)]
+pub struct WorkerPool {
+    /// The number of threads, zero meaning one for each core
+    threads: usize,
+
+    pool: Arc<OnceLock<Option<rayon::ThreadPool>>>,
+}
+
+impl WorkerPool {
+    pub fn new(threads: usize) -> Self {
+        Self {
+            threads,
+            pool: Arc::default(),
+        }
+    }
+
+    /// Gets the number of threads asked for, zero meaning one for each core
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Gets the threads of the pool, starting them if needed.
+    /// `None` when there is only one, or they could not be started
+    fn pool(&self) -> Option<&rayon::ThreadPool> {
+        self.pool
+            .get_or_init(|| {
+                let threads = resolve_worker_threads(self.threads);
+                if threads < 2 {
+                    return None;
+                }
+
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(threads)
+                    .thread_name(|index| format!("generation-worker-{index}"))
+                    .build()
+                    .ok()
+            })
+            .as_ref()
+    }
+}
+
+/// Splits `data`, made of rows of `row_len` items, into contiguous bands of rows
+/// and calls `f` with the rows range and the items of each band.
+/// Bands are processed in parallel on the threads of `workers`, and
+/// since each of them only writes its own items the outcome does not
+/// depend on the number of threads
+pub fn for_each_row_band<T, F>(data: &mut [T], row_len: usize, workers: &WorkerPool, f: F)
+where
+    T: Send,
+    F: Fn(Range<usize>, &mut [T]) + Sync,
+{
+    let rows = data.len().checked_div(row_len).unwrap_or(0);
+    let bands = resolve_worker_threads(workers.threads())
+        .min(rows / MIN_ROWS_PER_BAND)
+        .max(1);
+    let Some(pool) = workers.pool().filter(|_| bands > 1) else {
+        f(0..rows, data);
+        return;
+    };
+
+    let rows_per_band = rows.div_ceil(bands);
+    pool.install(|| {
+        data.par_chunks_mut(rows_per_band * row_len)
+            .enumerate()
+            .for_each(|(band, chunk)| {
+                let first_row = band * rows_per_band;
+                f(first_row..first_row + chunk.len() / row_len, chunk);
+            });
+    });
+}
@@ -18,16 +18,41 @@ impl Default for UniverseGridMode {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum UniverseCell {
-    Dead = 0,
-    Alive = 1,
+    Dead,
+    Alive,
+
+    /// A cell that stopped being alive but has not reached the dead state yet.
+    /// Only happens with multi-state ("Generations") rules, where the value
+    /// is the number of generations passed since the cell stopped being alive,
+    /// starting from 1
+    Dying(u8),
 }
 
 impl UniverseCell {
     pub fn is_alive(&self) -> bool {
         matches!(self, UniverseCell::Alive)
     }
+
+    /// Gets the numeric state of this cell: 0 is dead, 1 is alive and
+    /// any greater value is one of the dying states
+    pub fn state(&self) -> u8 {
+        match self {
+            UniverseCell::Dead => 0,
+            UniverseCell::Alive => 1,
+            UniverseCell::Dying(age) => age.saturating_add(1),
+        }
+    }
+
+    /// Builds a cell from its numeric state. See `UniverseCell::state`
+    pub fn from_state(state: u8) -> Self {
+        match state {
+            0 => UniverseCell::Dead,
+            1 => UniverseCell::Alive,
+            n => UniverseCell::Dying(n - 1),
+        }
+    }
 }
 
 impl std::ops::Not for UniverseCell {
@@ -35,7 +60,7 @@ impl std::ops::Not for UniverseCell {
     fn not(self) -> Self::Output {
         match self {
             UniverseCell::Alive => UniverseCell::Dead,
-            UniverseCell::Dead => UniverseCell::Alive,
+            UniverseCell::Dead | UniverseCell::Dying(_) => UniverseCell::Alive,
         }
     }
 }
@@ -45,9 +70,10 @@ impl fmt::Display for UniverseCell {
         write!(
             f,
             "{}",
-            match self.is_alive() {
-                true => "Alive",
-                _ => "Dead",
+            match self {
+                UniverseCell::Alive => "Alive",
+                UniverseCell::Dying(_) => "Dying",
+                UniverseCell::Dead => "Dead",
             }
         )
     }
@@ -90,6 +116,10 @@ impl UniversePoint {
     pub fn corpse_heat(&self) -> f64 {
         self.corpse_heat
     }
+
+    pub fn set_corpse_heat(&mut self, value: f64) {
+        self.corpse_heat = value;
+    }
 }
 
 pub trait UniversePointMatrix {
@@ -0,0 +1,665 @@
+use super::UniverseCell;
+use crate::i18n::i18n_noop;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// A list of well known rules, as (name, rulestring) pairs.
+/// Names are to be translated where they are shown
+pub const UNIVERSE_RULE_PRESETS: [(&str, &str); 16] = [
+    (i18n_noop("Conway's Life"), "B3/S23"),
+    (i18n_noop("HighLife"), "B36/S23"),
+    (i18n_noop("Seeds"), "B2/S"),
+    (i18n_noop("Day & Night"), "B3678/S34678"),
+    (i18n_noop("Life without death"), "B3/S012345678"),
+    (i18n_noop("Replicator"), "B1357/S1357"),
+    (i18n_noop("Maze"), "B3/S12345"),
+    (i18n_noop("2x2"), "B36/S125"),
+    (i18n_noop("Diamoeba"), "B35678/S5678"),
+    (i18n_noop("Morley"), "B368/S245"),
+    (i18n_noop("Brian's Brain"), "B2/S/C3"),
+    (i18n_noop("Star Wars"), "B2/S345/C4"),
+    (i18n_noop("Hexagonal Life"), "B2/S34H"),
+    (i18n_noop("Bosco's Rule"), "R5,C0,M1,S34..58,B34..45,NM"),
+    (i18n_noop("Majority"), "R4,C0,M1,S41..81,B41..81,NM"),
+    (i18n_noop("Waffle"), "R7,C0,M1,S100..200,B75..170,NM"),
+];
+
+/// The maximum neighbourhood range allowed for Larger than Life rules
+pub const UNIVERSE_RULE_MAX_RANGE: u8 = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleParseError {
+    /// The rulestring is empty
+    Empty,
+
+    /// The rulestring does not have the `B<digits>/S<digits>` (or `<survival>/<birth>`) shape,
+    /// nor the Larger than Life `R<range>,C<states>,M<0|1>,S<min>..<max>,B<min>..<max>,N<M|N>` one
+    Malformed,
+
+    /// A neighbour count is not a valid digit for the neighbourhood
+    InvalidCount(char),
+
+    /// The number of states is not a number between 2 and 255
+    InvalidStates,
+
+    /// The neighbourhood range is not a number between 1 and `UNIVERSE_RULE_MAX_RANGE`
+    InvalidRange,
+}
+
+impl fmt::Display for RuleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleParseError::Empty => write!(f, "Empty rule"),
+            RuleParseError::Malformed => write!(f, "Malformed rule"),
+            RuleParseError::InvalidCount(c) => write!(f, "Invalid neighbour count '{c}'"),
+            RuleParseError::InvalidStates => write!(f, "Invalid number of states"),
+            RuleParseError::InvalidRange => write!(f, "Invalid neighbourhood range"),
+        }
+    }
+}
+
+impl std::error::Error for RuleParseError {}
+
+/// The shape of the area around a cell in which living neighbours are counted
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum UniverseNeighbourhood {
+    /// All the cells within `range` rows and columns (a square)
+    #[default]
+    Moore,
+
+    /// All the cells within a Manhattan distance of `range` (a diamond)
+    VonNeumann,
+
+    /// The 6 cells around a cell of a hexagonal grid. Odd rows of the grid
+    /// are shifted right by half a cell
+    Hexagonal,
+
+    /// The 12 cells sharing an edge or a vertex with a cell of a triangular grid.
+    /// Cells whose row and column sum up to an even number point upwards
+    Triangular,
+}
+
+const HEXAGONAL_EVEN_ROW_OFFSETS: [(isize, isize); 6] =
+    [(-1, -1), (-1, 0), (0, -1), (0, 1), (1, -1), (1, 0)];
+
+const HEXAGONAL_ODD_ROW_OFFSETS: [(isize, isize); 6] =
+    [(-1, 0), (-1, 1), (0, -1), (0, 1), (1, 0), (1, 1)];
+
+const TRIANGULAR_UPWARD_OFFSETS: [(isize, isize); 12] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -2),
+    (0, -1),
+    (0, 1),
+    (0, 2),
+    (1, -2),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (1, 2),
+];
+
+const TRIANGULAR_DOWNWARD_OFFSETS: [(isize, isize); 12] = [
+    (-1, -2),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (-1, 2),
+    (0, -2),
+    (0, -1),
+    (0, 1),
+    (0, 2),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+impl UniverseNeighbourhood {
+    /// Gets the (row, column) offsets of the immediate neighbours of the cell
+    /// at `row`x`column`, for the grids where they depend on the cell position.
+    /// Returns `None` for square grids
+    pub fn grid_offsets(&self, row: usize, column: usize) -> Option<&'static [(isize, isize)]> {
+        match self {
+            UniverseNeighbourhood::Moore | UniverseNeighbourhood::VonNeumann => None,
+            UniverseNeighbourhood::Hexagonal if row.is_multiple_of(2) => {
+                Some(&HEXAGONAL_EVEN_ROW_OFFSETS)
+            }
+            UniverseNeighbourhood::Hexagonal => Some(&HEXAGONAL_ODD_ROW_OFFSETS),
+            UniverseNeighbourhood::Triangular if (row + column).is_multiple_of(2) => {
+                Some(&TRIANGULAR_UPWARD_OFFSETS)
+            }
+            UniverseNeighbourhood::Triangular => Some(&TRIANGULAR_DOWNWARD_OFFSETS),
+        }
+    }
+}
+
+/// The neighbour counts causing a birth or a survival
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum NeighbourCounts {
+    /// Any set of counts between 0 and 15, one bit per count
+    Set(u16),
+
+    /// All the counts between the two values, inclusive
+    Interval(u16, u16),
+}
+
+impl NeighbourCounts {
+    fn contains(&self, count: u16) -> bool {
+        match self {
+            NeighbourCounts::Set(mask) => count < 16 && mask & (1 << count) != 0,
+            NeighbourCounts::Interval(min, max) => (*min..=*max).contains(&count),
+        }
+    }
+}
+
+/// A Life-like (outer totalistic) rule, expressed in B/S notation.
+/// A dead cell is born if the number of its living neighbours is in the
+/// `birth` set, and a living cell survives if it is in the `survival` set.
+///
+/// Rules with more than two `states` are "Generations" rules (B/S/C notation):
+/// a cell that does not survive goes through `states - 2` dying states, in which
+/// it does not count as a living neighbour, before becoming dead.
+///
+/// Larger than Life rules count neighbours in a `range` wider than the
+/// immediate one, optionally including the cell itself, and use intervals
+/// of counts for births and survivals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct UniverseRule {
+    birth: NeighbourCounts,
+    survival: NeighbourCounts,
+    states: u8,
+    neighbourhood: UniverseNeighbourhood,
+    range: u8,
+    include_center: bool,
+}
+
+impl Default for UniverseRule {
+    fn default() -> Self {
+        Self::conway()
+    }
+}
+
+impl UniverseRule {
+    /// Builds a rule from the neighbour counts causing births and survivals
+    pub fn new(birth: &[u8], survival: &[u8]) -> Self {
+        let mask = |counts: &[u8]| {
+            counts
+                .iter()
+                .filter(|count| **count < 16)
+                .fold(0u16, |mask, count| mask | (1 << count))
+        };
+
+        Self {
+            birth: NeighbourCounts::Set(mask(birth)),
+            survival: NeighbourCounts::Set(mask(survival)),
+            states: 2,
+            neighbourhood: UniverseNeighbourhood::Moore,
+            range: 1,
+            include_center: false,
+        }
+    }
+
+    /// Builds a "Generations" rule with `states` total cell states
+    pub fn generations(birth: &[u8], survival: &[u8], states: u8) -> Self {
+        Self {
+            states: states.max(2),
+            ..Self::new(birth, survival)
+        }
+    }
+
+    /// Builds a Larger than Life rule, where births and survivals happen when the
+    /// count of living cells in a `neighbourhood` of the given `range` falls in
+    /// the `birth` or `survival` intervals
+    pub fn larger_than_life(
+        range: u8,
+        states: u8,
+        include_center: bool,
+        survival: (u16, u16),
+        birth: (u16, u16),
+        neighbourhood: UniverseNeighbourhood,
+    ) -> Self {
+        Self {
+            birth: NeighbourCounts::Interval(birth.0, birth.1),
+            survival: NeighbourCounts::Interval(survival.0, survival.1),
+            states: states.max(2),
+            neighbourhood,
+            range: range.clamp(1, UNIVERSE_RULE_MAX_RANGE),
+            include_center,
+        }
+    }
+
+    /// Conway's Game of Life, B3/S23
+    pub fn conway() -> Self {
+        Self::new(&[3], &[2, 3])
+    }
+
+    /// Whether a dead cell with `alive_neighbours` living neighbours is born
+    pub fn is_birth(&self, alive_neighbours: u16) -> bool {
+        self.birth.contains(alive_neighbours)
+    }
+
+    /// Whether a living cell with `alive_neighbours` living neighbours survives
+    pub fn is_survival(&self, alive_neighbours: u16) -> bool {
+        self.survival.contains(alive_neighbours)
+    }
+
+    /// The number of states a cell can be in, including dead and alive
+    pub fn states(&self) -> u8 {
+        self.states
+    }
+
+    /// Whether this rule has dying states between alive and dead
+    pub fn is_generations(&self) -> bool {
+        self.states > 2
+    }
+
+    /// The shape of the area where neighbours are counted
+    pub fn neighbourhood(&self) -> UniverseNeighbourhood {
+        self.neighbourhood
+    }
+
+    /// How far from a cell its neighbours can be
+    pub fn range(&self) -> u8 {
+        self.range
+    }
+
+    /// Gets this rule with its neighbourhood range narrowed down to `max_range`,
+    /// when it is wider
+    pub fn with_max_range(&self, max_range: u8) -> Self {
+        Self {
+            range: self.range.min(max_range.max(1)),
+            ..*self
+        }
+    }
+
+    /// How many rows and columns away from a cell its neighbours can be
+    pub fn reach(&self) -> (usize, usize) {
+        match self.neighbourhood {
+            UniverseNeighbourhood::Hexagonal => (1, 1),
+            UniverseNeighbourhood::Triangular => (1, 2),
+            _ => (self.range as usize, self.range as usize),
+        }
+    }
+
+    /// Whether a cell counts itself among its neighbours
+    pub fn include_center(&self) -> bool {
+        self.include_center
+    }
+
+    /// Whether this rule only looks at the 8 immediate neighbours of a cell
+    pub fn is_life_like(&self) -> bool {
+        matches!(self.neighbourhood, UniverseNeighbourhood::Moore)
+            && self.range == 1
+            && !self.include_center
+    }
+
+    /// The highest number of living neighbours a cell can have with this rule
+    pub fn max_neighbours(&self) -> u16 {
+        let range = self.range as u16;
+        let area = match self.neighbourhood {
+            UniverseNeighbourhood::Moore => (2 * range + 1) * (2 * range + 1),
+            UniverseNeighbourhood::VonNeumann => 2 * range * (range + 1) + 1,
+            UniverseNeighbourhood::Hexagonal => 7,
+            UniverseNeighbourhood::Triangular => 13,
+        };
+
+        if self.include_center {
+            area
+        } else {
+            area - 1
+        }
+    }
+
+    /// Computes the next state of a cell in `current` state with
+    /// `alive_neighbours` living neighbours
+    pub fn next_cell(&self, current: UniverseCell, alive_neighbours: u16) -> UniverseCell {
+        match current {
+            UniverseCell::Alive if self.is_survival(alive_neighbours) => UniverseCell::Alive,
+            UniverseCell::Alive if self.is_generations() => UniverseCell::Dying(1),
+            UniverseCell::Alive => UniverseCell::Dead,
+            UniverseCell::Dying(age) if age.saturating_add(2) < self.states => {
+                UniverseCell::Dying(age + 1)
+            }
+            UniverseCell::Dying(_) => UniverseCell::Dead,
+            UniverseCell::Dead if self.is_birth(alive_neighbours) => UniverseCell::Alive,
+            UniverseCell::Dead => UniverseCell::Dead,
+        }
+    }
+
+    /// The neighbour counts causing a birth, in ascending order
+    pub fn birth_counts(&self) -> Vec<u16> {
+        (0..=self.max_neighbours())
+            .filter(|c| self.is_birth(*c))
+            .collect()
+    }
+
+    /// The neighbour counts allowing a survival, in ascending order
+    pub fn survival_counts(&self) -> Vec<u16> {
+        (0..=self.max_neighbours())
+            .filter(|c| self.is_survival(*c))
+            .collect()
+    }
+
+    /// Gets the position of this rule among the `UNIVERSE_RULE_PRESETS`,
+    /// if it is one of them
+    pub fn preset_index(&self) -> Option<usize> {
+        UNIVERSE_RULE_PRESETS
+            .iter()
+            .position(|(_, rulestring)| rulestring.parse::<UniverseRule>().as_ref() == Ok(self))
+    }
+}
+
+fn parse_counts(digits: &str) -> Result<Vec<u8>, RuleParseError> {
+    digits
+        .chars()
+        .map(|c| match c.to_digit(10) {
+            Some(count) => Ok(count as u8),
+            _ => Err(RuleParseError::InvalidCount(c)),
+        })
+        .collect()
+}
+
+fn parse_states(digits: &str) -> Result<u8, RuleParseError> {
+    match digits.parse::<u8>() {
+        Ok(states) if states >= 2 => Ok(states),
+        _ => Err(RuleParseError::InvalidStates),
+    }
+}
+
+/// Parses a Larger than Life interval, like `34..58`
+fn parse_interval(interval: &str) -> Result<(u16, u16), RuleParseError> {
+    let (min, max) = interval.split_once("..").ok_or(RuleParseError::Malformed)?;
+    let bound = |value: &str| {
+        let value = value.trim();
+        value
+            .parse::<u16>()
+            .map_err(|_| RuleParseError::InvalidCount(value.chars().next().unwrap_or('.')))
+    };
+
+    Ok((bound(min)?, bound(max)?))
+}
+
+/// Parses a Larger than Life rulestring, like `R5,C0,M1,S34..58,B34..45,NM`
+fn parse_larger_than_life(s: &str) -> Result<UniverseRule, RuleParseError> {
+    let (mut range, mut states, mut include_center) = (None, 0u8, false);
+    let (mut survival, mut birth) = (None, None);
+    let mut neighbourhood = UniverseNeighbourhood::Moore;
+
+    for token in s.split(',').map(str::trim) {
+        let mut chars = token.chars();
+        let key = chars.next().map(|c| c.to_ascii_uppercase());
+        let value = chars.as_str();
+        match key {
+            Some('R') => match value.parse::<u8>() {
+                Ok(r) if (1..=UNIVERSE_RULE_MAX_RANGE).contains(&r) => range = Some(r),
+                _ => return Err(RuleParseError::InvalidRange),
+            },
+            Some('C') => {
+                states = value
+                    .parse::<u8>()
+                    .map_err(|_| RuleParseError::InvalidStates)?
+            }
+            Some('M') => match value {
+                "0" => include_center = false,
+                "1" => include_center = true,
+                _ => return Err(RuleParseError::Malformed),
+            },
+            Some('S') => survival = Some(parse_interval(value)?),
+            Some('B') => birth = Some(parse_interval(value)?),
+            Some('N') => match value.to_ascii_uppercase().as_str() {
+                "M" => neighbourhood = UniverseNeighbourhood::Moore,
+                "N" => neighbourhood = UniverseNeighbourhood::VonNeumann,
+                _ => return Err(RuleParseError::Malformed),
+            },
+            _ => return Err(RuleParseError::Malformed),
+        }
+    }
+
+    match (range, survival, birth) {
+        (Some(range), Some(survival), Some(birth)) => Ok(UniverseRule::larger_than_life(
+            range,
+            states,
+            include_center,
+            survival,
+            birth,
+            neighbourhood,
+        )),
+        _ => Err(RuleParseError::Malformed),
+    }
+}
+
+impl FromStr for UniverseRule {
+    type Err = RuleParseError;
+
+    /// Parses a rulestring in B/S or B/S/C notation (`B36/S23`, `B2/S/C3`, in
+    /// any order and case), in the classic S/B and S/B/C notations (`23/36`, `/2/3`)
+    /// or in the Larger than Life notation (`R5,C0,M1,S34..58,B34..45,NM`).
+    /// B/S rules ending with `H` run on a hexagonal grid, and the ones ending with
+    /// `L` on a triangular grid
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(RuleParseError::Empty);
+        }
+
+        if s.contains(',') {
+            return parse_larger_than_life(s);
+        }
+
+        let (s, neighbourhood) = match s.chars().last().map(|c| c.to_ascii_uppercase()) {
+            Some('H') => (&s[..s.len() - 1], UniverseNeighbourhood::Hexagonal),
+            Some('L') => (&s[..s.len() - 1], UniverseNeighbourhood::Triangular),
+            _ => (s, UniverseNeighbourhood::Moore),
+        };
+
+        let parts: Vec<&str> = s.split('/').map(str::trim).collect();
+        if parts.len() != 2 && parts.len() != 3 {
+            return Err(RuleParseError::Malformed);
+        }
+
+        let lettered = parts
+            .iter()
+            .any(|part| part.starts_with(|c: char| c.is_ascii_alphabetic()));
+
+        let (birth, survival, states) = if lettered {
+            let (mut birth, mut survival, mut states) = (None, None, None);
+            for part in parts.iter() {
+                let mut chars = part.chars();
+                match chars.next().map(|c| c.to_ascii_uppercase()) {
+                    Some('B') if birth.is_none() => birth = Some(parse_counts(chars.as_str())?),
+                    Some('S') if survival.is_none() => {
+                        survival = Some(parse_counts(chars.as_str())?)
+                    }
+                    Some('C') if states.is_none() => states = Some(parse_states(chars.as_str())?),
+                    _ => return Err(RuleParseError::Malformed),
+                }
+            }
+
+            match (birth, survival) {
+                (Some(birth), Some(survival)) => (birth, survival, states.unwrap_or(2)),
+                _ => return Err(RuleParseError::Malformed),
+            }
+        } else {
+            // Classic notation, survival counts first
+            let survival = parse_counts(parts[0])?;
+            let birth = parse_counts(parts[1])?;
+            let states = match parts.get(2) {
+                Some(states) => parse_states(states)?,
+                None => 2,
+            };
+            (birth, survival, states)
+        };
+
+        let rule = Self {
+            neighbourhood,
+            ..Self::generations(&birth, &survival, states)
+        };
+
+        let max_neighbours = rule.max_neighbours();
+        if let Some(count) = birth
+            .iter()
+            .chain(survival.iter())
+            .find(|count| **count as u16 > max_neighbours)
+        {
+            return Err(RuleParseError::InvalidCount(
+                char::from_digit(*count as u32, 10).unwrap_or('?'),
+            ));
+        }
+
+        Ok(rule)
+    }
+}
+
+impl TryFrom<String> for UniverseRule {
+    type Error = RuleParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<UniverseRule> for String {
+    fn from(rule: UniverseRule) -> Self {
+        rule.to_string()
+    }
+}
+
+impl fmt::Display for UniverseRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.birth, self.survival) {
+            (NeighbourCounts::Interval(b_min, b_max), NeighbourCounts::Interval(s_min, s_max)) => {
+                let states = if self.is_generations() {
+                    self.states
+                } else {
+                    0
+                };
+                let neighbourhood = match self.neighbourhood {
+                    UniverseNeighbourhood::VonNeumann => 'N',
+                    _ => 'M',
+                };
+                write!(
+                    f,
+                    "R{},C{},M{},S{}..{},B{}..{},N{}",
+                    self.range,
+                    states,
+                    self.include_center as u8,
+                    s_min,
+                    s_max,
+                    b_min,
+                    b_max,
+                    neighbourhood
+                )
+            }
+            _ => {
+                let digits = |counts: Vec<u16>| {
+                    counts
+                        .iter()
+                        .map(|count| count.to_string())
+                        .collect::<String>()
+                };
+
+                write!(
+                    f,
+                    "B{}/S{}",
+                    digits(self.birth_counts()),
+                    digits(self.survival_counts())
+                )?;
+
+                if self.is_generations() {
+                    write!(f, "/C{}", self.states)?;
+                }
+
+                match self.neighbourhood {
+                    UniverseNeighbourhood::Hexagonal => write!(f, "H"),
+                    UniverseNeighbourhood::Triangular => write!(f, "L"),
+                    _ => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(rulestring: &str) -> UniverseRule {
+        rulestring.parse().unwrap()
+    }
+
+    #[test]
+    fn presets_round_trip() {
+        for (index, (name, rulestring)) in UNIVERSE_RULE_PRESETS.iter().enumerate() {
+            let rule = parse(rulestring);
+            assert_eq!(rule.to_string(), *rulestring, "{name}");
+            assert_eq!(parse(&rule.to_string()), rule, "{name}");
+            assert_eq!(rule.preset_index(), Some(index), "{name}");
+        }
+    }
+
+    #[test]
+    fn notations_give_the_same_rule() {
+        let conway = UniverseRule::conway();
+        for rulestring in ["B3/S23", "b3/s23", "S23/B3", " B3/S23 ", "23/3"] {
+            assert_eq!(parse(rulestring), conway, "{rulestring}");
+        }
+        assert_eq!(parse("/2/3"), parse("B2/S/C3"));
+        assert_eq!(parse("B36/S23").preset_index(), Some(1));
+        assert_eq!(parse("B36/S234").preset_index(), None);
+    }
+
+    #[test]
+    fn suffixes_pick_the_neighbourhood() {
+        for (rulestring, neighbourhood) in [
+            ("B2/S34H", UniverseNeighbourhood::Hexagonal),
+            ("B4/S34L", UniverseNeighbourhood::Triangular),
+            ("B3/S23", UniverseNeighbourhood::Moore),
+        ] {
+            let rule = parse(rulestring);
+            assert_eq!(rule.neighbourhood(), neighbourhood, "{rulestring}");
+            assert_eq!(rule.to_string(), rulestring);
+        }
+        assert_eq!(parse("b2/s34h"), parse("B2/S34H"));
+        assert_eq!(
+            "B7/S2H".parse::<UniverseRule>(),
+            Err(RuleParseError::InvalidCount('7'))
+        );
+    }
+
+    #[test]
+    fn generations_keep_their_states() {
+        let rule = parse("B2/S345/C4");
+        assert_eq!(rule.states(), 4);
+        assert!(rule.is_generations());
+        assert_eq!(rule.birth_counts(), vec![2]);
+        assert_eq!(rule.survival_counts(), vec![3, 4, 5]);
+
+        let hexagonal = parse("B2/S3/C5H");
+        assert_eq!(hexagonal.states(), 5);
+        assert_eq!(hexagonal.to_string(), "B2/S3/C5H");
+
+        assert!(!parse("B3/S23/C2").is_generations());
+        assert_eq!(parse("B3/S23/C2").to_string(), "B3/S23");
+    }
+
+    #[test]
+    fn malformed_rules_are_refused() {
+        for (rulestring, error) in [
+            ("", RuleParseError::Empty),
+            ("B3", RuleParseError::Malformed),
+            ("B3/S23/C3/X", RuleParseError::Malformed),
+            ("B3/S2x", RuleParseError::InvalidCount('x')),
+            ("B9/S23", RuleParseError::InvalidCount('9')),
+            ("B3/S23/C1", RuleParseError::InvalidStates),
+            ("R0,C0,M0,S2..3,B3..3,NM", RuleParseError::InvalidRange),
+        ] {
+            assert_eq!(
+                rulestring.parse::<UniverseRule>(),
+                Err(error),
+                "{rulestring}"
+            );
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The share of living cells in soups, unless told otherwise
+pub const SOUP_DEFAULT_DENSITY: f64 = 0.4;
+
+/// The symmetry random soups are filled with
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoupSymmetry {
+    /// Every cell is drawn on its own
+    #[default]
+    None,
+
+    /// Cells are the same as the ones rotated by a half turn around the center
+    C2,
+
+    /// Cells are the same as the ones rotated by a quarter turn around the center.
+    /// The soup is restricted to a centered square
+    C4,
+
+    /// Cells are the same as the ones rotated by a quarter turn around the center,
+    /// and as the ones mirrored along both axes and both diagonals.
+    /// The soup is restricted to a centered square
+    D8,
+}
+
+impl SoupSymmetry {
+    /// All the symmetries, in the order they are offered to the user
+    pub const ALL: [SoupSymmetry; 4] = [
+        SoupSymmetry::None,
+        SoupSymmetry::C2,
+        SoupSymmetry::C4,
+        SoupSymmetry::D8,
+    ];
+
+    /// Whether soups with this symmetry have to be square
+    fn is_square(&self) -> bool {
+        matches!(self, SoupSymmetry::C4 | SoupSymmetry::D8)
+    }
+
+    /// Calls `f` with every cell the cell at `row`x`column` is mapped to by this
+    /// symmetry, the cell itself included, within a `rows`x`columns` area
+    fn for_each_image<F: FnMut(usize, usize)>(
+        &self,
+        row: usize,
+        column: usize,
+        rows: usize,
+        columns: usize,
+        mut f: F,
+    ) {
+        let (last_row, last_column) = (rows - 1, columns - 1);
+        f(row, column);
+        match self {
+            SoupSymmetry::None => (),
+            SoupSymmetry::C2 => f(last_row - row, last_column - column),
+            SoupSymmetry::C4 => {
+                f(column, last_row - row);
+                f(last_row - row, last_column - column);
+                f(last_column - column, row);
+            }
+            SoupSymmetry::D8 => {
+                f(column, last_row - row);
+                f(last_row - row, last_column - column);
+                f(last_column - column, row);
+                f(row, last_column - column);
+                f(last_row - row, column);
+                f(column, row);
+                f(last_column - column, last_row - row);
+            }
+        }
+    }
+}
+
+impl fmt::Display for SoupSymmetry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            SoupSymmetry::None => "none",
+            SoupSymmetry::C2 => "c2",
+            SoupSymmetry::C4 => "c4",
+            SoupSymmetry::D8 => "d8",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownSymmetryError;
+
+impl fmt::Display for UnknownSymmetryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown soup symmetry")
+    }
+}
+
+impl std::error::Error for UnknownSymmetryError {}
+
+impl FromStr for SoupSymmetry {
+    type Err = UnknownSymmetryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SoupSymmetry::ALL
+            .into_iter()
+            .find(|symmetry| symmetry.to_string() == s)
+            .ok_or(UnknownSymmetryError)
+    }
+}
+
+/// Describes how a random soup is filled. The same options always
+/// fill a universe of a given size with the same cells, on every
+/// platform and with every version of the application
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct SoupOptions {
+    seed: u64,
+
+    /// The chance of each cell being alive, from 0 to 1
+    density: f64,
+
+    /// The side of the centered box the soup is restricted to,
+    /// `None` filling the whole universe
+    central_box: Option<usize>,
+
+    symmetry: SoupSymmetry,
+}
+
+impl Default for SoupOptions {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl SoupOptions {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            density: SOUP_DEFAULT_DENSITY,
+            central_box: None,
+            symmetry: SoupSymmetry::None,
+        }
+    }
+
+    /// Options with a seed drawn at random
+    pub fn random() -> Self {
+        Self::new(rand::random())
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn density(&self) -> f64 {
+        self.density
+    }
+
+    pub fn set_density(&mut self, value: f64) {
+        self.density = value.clamp(0.0, 1.0);
+    }
+
+    pub fn central_box(&self) -> Option<usize> {
+        self.central_box
+    }
+
+    pub fn set_central_box(&mut self, value: Option<usize>) {
+        self.central_box = value.filter(|side| *side > 0);
+    }
+
+    pub fn symmetry(&self) -> SoupSymmetry {
+        self.symmetry
+    }
+
+    pub fn set_symmetry(&mut self, value: SoupSymmetry) {
+        self.symmetry = value;
+    }
+
+    /// Calls `f` with the row and the column of every living cell
+    /// of the soup filling a `rows`x`columns` universe.
+    /// Cells are drawn row by row, and the cells a symmetry maps
+    /// a drawn cell onto take its state rather than being drawn again
+    pub fn fill<F: FnMut(usize, usize)>(&self, rows: usize, columns: usize, mut f: F) {
+        let (mut area_rows, mut area_columns) = match self.central_box {
+            Some(side) => (side.min(rows), side.min(columns)),
+            None => (rows, columns),
+        };
+        if self.symmetry.is_square() {
+            area_rows = area_rows.min(area_columns);
+            area_columns = area_rows;
+        }
+        if area_rows == 0 || area_columns == 0 {
+            return;
+        }
+
+        let (top, left) = ((rows - area_rows) / 2, (columns - area_columns) / 2);
+        // Unlike the standard generator, this one is guaranteed to draw the same
+        // numbers from a seed across releases, which seeds shared by users rely on
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        let mut drawn = vec![false; area_rows * area_columns];
+        for row in 0..area_rows {
+            for column in 0..area_columns {
+                if drawn[row * area_columns + column] {
+                    continue;
+                }
+
+                let alive = rng.gen_bool(self.density);
+                self.symmetry.for_each_image(
+                    row,
+                    column,
+                    area_rows,
+                    area_columns,
+                    |row, column| {
+                        let index = row * area_columns + column;
+                        if !drawn[index] {
+                            drawn[index] = true;
+                            if alive {
+                                f(top + row, left + column);
+                            }
+                        }
+                    },
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(options: &SoupOptions, rows: usize, columns: usize) -> Vec<(usize, usize)> {
+        let mut cells = vec![];
+        options.fill(rows, columns, |row, column| cells.push((row, column)));
+        cells
+    }
+
+    #[test]
+    fn seeds_give_the_same_soup() {
+        let mut options = SoupOptions::new(1);
+        assert_eq!(cells(&options, 40, 50), cells(&options, 40, 50));
+        assert_ne!(cells(&options, 40, 50), cells(&SoupOptions::new(2), 40, 50));
+
+        for symmetry in SoupSymmetry::ALL {
+            options.set_symmetry(symmetry);
+            options.set_density(0.3);
+            options.set_central_box(Some(24));
+            assert_eq!(
+                cells(&options, 40, 50),
+                cells(&options, 40, 50),
+                "{symmetry}"
+            );
+        }
+    }
+
+    #[test]
+    fn seeds_give_the_same_soup_across_releases() {
+        assert_eq!(
+            cells(&SoupOptions::new(1), 4, 4),
+            [(0, 1), (0, 3), (1, 0), (1, 3), (3, 0), (3, 1), (3, 3)]
+        );
+    }
+
+    #[test]
+    fn soups_keep_to_their_box_and_symmetry() {
+        let mut options = SoupOptions::new(3);
+        options.set_central_box(Some(10));
+        options.set_symmetry(SoupSymmetry::C2);
+        let soup = cells(&options, 30, 40);
+        assert!(!soup.is_empty());
+        for (row, column) in soup.iter() {
+            assert!((10..20).contains(row) && (15..25).contains(column));
+            assert!(soup.contains(&(29 - row, 39 - column)));
+        }
+    }
+}
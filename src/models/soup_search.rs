@@ -0,0 +1,443 @@
+use super::parallel::resolve_worker_threads;
+use super::{
+    Census, Motion, ObjectKind, SoupOptions, StabilizationDetector, Universe, UniverseRule,
+    UniverseTopology, SOUP_DEFAULT_DENSITY,
+};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The generations a soup is run for at most, before being given up as unstable
+pub const SOUP_SEARCH_MAX_GENERATIONS: u64 = 20_000;
+
+/// Soups whose census did not change over this many generations, with
+/// every object identified, are taken as stable. Spaceships flying away
+/// keep soups from ever repeating the same states
+const SOUP_SEARCH_CENSUS_INTERVAL: u64 = 500;
+
+/// Objects found in no more than one soup out of this many are rare
+const SOUP_SEARCH_RARE_SHARE: u64 = 1000;
+
+/// The seeds kept for each kind of object, and for unstable soups
+const SOUP_SEARCH_SEEDS_KEPT: usize = 10;
+
+/// What a soup search runs: `soups` soups of `side`x`side` cells, drawn
+/// from consecutive seeds, each one evolving on its own unbounded plane
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoupSearchOptions {
+    soups: u64,
+    first_seed: u64,
+    side: usize,
+
+    /// The chance of each cell of the soups being alive, from 0 to 1
+    density: f64,
+
+    rule: UniverseRule,
+
+    /// The number of soups run at once, zero meaning one for each core
+    threads: usize,
+}
+
+impl Default for SoupSearchOptions {
+    fn default() -> Self {
+        Self {
+            soups: 1000,
+            first_seed: 0,
+            side: 16,
+            density: SOUP_DEFAULT_DENSITY,
+            rule: UniverseRule::conway(),
+            threads: 0,
+        }
+    }
+}
+
+impl SoupSearchOptions {
+    pub fn soups(&self) -> u64 {
+        self.soups
+    }
+
+    pub fn set_soups(&mut self, value: u64) {
+        self.soups = value;
+    }
+
+    pub fn set_first_seed(&mut self, value: u64) {
+        self.first_seed = value;
+    }
+
+    pub fn set_side(&mut self, value: usize) {
+        self.side = value.max(1);
+    }
+
+    pub fn set_density(&mut self, value: f64) {
+        self.density = value.clamp(0.0, 1.0);
+    }
+
+    pub fn set_rule(&mut self, value: UniverseRule) {
+        self.rule = value;
+    }
+
+    pub fn set_threads(&mut self, value: usize) {
+        self.threads = value;
+    }
+
+    /// Gets the options of the soup drawn from `seed`
+    pub fn soup(&self, seed: u64) -> SoupOptions {
+        let mut soup = SoupOptions::new(seed);
+        soup.set_density(self.density);
+        soup
+    }
+}
+
+/// A kind of object found by a soup search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SoupSearchTally {
+    code: String,
+    name: Option<&'static str>,
+
+    /// The kind of the objects, spaceships being taken as moving by
+    /// positive rows and columns, rows first, whichever way they flew
+    kind: ObjectKind,
+
+    /// The number of objects found
+    objects: u64,
+
+    /// The number of soups the objects were found in
+    soups: u64,
+
+    /// The first seeds of the soups the objects were found in, by increasing seed
+    seeds: Vec<u64>,
+}
+
+impl SoupSearchTally {
+    /// Adds the objects and the soups of `other`, keeping the lowest seeds
+    fn merge(&mut self, other: SoupSearchTally) {
+        self.objects += other.objects;
+        self.soups += other.soups;
+        self.seeds.extend(other.seeds);
+        self.seeds.sort_unstable();
+        self.seeds.truncate(SOUP_SEARCH_SEEDS_KEPT);
+    }
+}
+
+/// The objects soups turned into, tallied by apgcode
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SoupSearchResults {
+    options: SoupSearchOptions,
+    tallies: HashMap<String, SoupSearchTally>,
+
+    /// Objects of stable soups that could not be identified
+    unidentified_objects: u64,
+
+    /// The number of soups that did not stabilize within `SOUP_SEARCH_MAX_GENERATIONS`
+    unstable_soups: u64,
+
+    /// The first seeds of the soups that did not stabilize
+    unstable_seeds: Vec<u64>,
+
+    /// The generations the soups ran for, all together
+    generations: u64,
+
+    elapsed: Duration,
+}
+
+impl SoupSearchResults {
+    /// Gets the kinds of objects found, the most common first
+    pub fn tallies(&self) -> Vec<&SoupSearchTally> {
+        let mut tallies: Vec<&SoupSearchTally> = self.tallies.values().collect();
+        tallies.sort_by(|a, b| b.objects.cmp(&a.objects).then_with(|| a.code.cmp(&b.code)));
+        tallies
+    }
+
+    /// Whether the objects of `tally` are rare: found in no more than
+    /// one soup out of `SOUP_SEARCH_RARE_SHARE`, and at least in one
+    pub fn is_rare(&self, tally: &SoupSearchTally) -> bool {
+        tally.soups <= (self.options.soups / SOUP_SEARCH_RARE_SHARE).max(1)
+    }
+
+    /// Gets the kinds of rare objects found, the rarest first
+    pub fn rare_tallies(&self) -> Vec<&SoupSearchTally> {
+        let mut tallies: Vec<&SoupSearchTally> = self
+            .tallies
+            .values()
+            .filter(|tally| self.is_rare(tally))
+            .collect();
+        tallies.sort_by(|a, b| a.soups.cmp(&b.soups).then_with(|| a.code.cmp(&b.code)));
+        tallies
+    }
+
+    /// Gets the number of objects found, unidentified ones included
+    pub fn objects(&self) -> u64 {
+        self.tallies
+            .values()
+            .map(|tally| tally.objects)
+            .sum::<u64>()
+            + self.unidentified_objects
+    }
+
+    /// Writes a summary of the search: what was searched, and the count of
+    /// every kind of object found, rare ones being marked with an asterisk
+    pub fn report(&self) -> String {
+        let options = &self.options;
+        let mut report = String::new();
+        let _ = writeln!(report, "Soup search report");
+        let _ = writeln!(report);
+        let _ = writeln!(report, "Rule: {}", options.rule);
+        let _ = writeln!(
+            report,
+            "Soups: {} of {}x{} cells at {}% density, seeds {} to {}",
+            options.soups,
+            options.side,
+            options.side,
+            (options.density * 100.0).round(),
+            options.first_seed,
+            options
+                .first_seed
+                .wrapping_add(options.soups.saturating_sub(1))
+        );
+        let _ = writeln!(
+            report,
+            "Generations: {}, in {:.1} seconds",
+            self.generations,
+            self.elapsed.as_secs_f64()
+        );
+        let _ = writeln!(
+            report,
+            "Objects: {}, of which {} unidentified",
+            self.objects(),
+            self.unidentified_objects
+        );
+        let _ = writeln!(
+            report,
+            "Unstable soups: {}{}",
+            self.unstable_soups,
+            seeds_list(&self.unstable_seeds, " (seeds ", ")")
+        );
+        let _ = writeln!(report);
+
+        let _ = writeln!(
+            report,
+            "{:>12}  {:>10}  {:<24}  {:<12}  Name",
+            "Objects", "Soups", "Code", "Kind"
+        );
+        for tally in self.tallies() {
+            let _ = writeln!(
+                report,
+                "{:>12}  {:>10}  {:<24}  {:<12}  {}{}",
+                tally.objects,
+                tally.soups,
+                tally.code,
+                kind_name(tally.kind),
+                tally.name.unwrap_or_default(),
+                if self.is_rare(tally) { " *" } else { "" }
+            );
+        }
+
+        let rare = self.rare_tallies();
+        if !rare.is_empty() {
+            let _ = writeln!(report);
+            let _ = writeln!(report, "* Rare objects, with the seeds of their soups:");
+            for tally in rare {
+                let _ = writeln!(
+                    report,
+                    "  {}{}",
+                    tally.code,
+                    seeds_list(&tally.seeds, ": ", "")
+                );
+            }
+        }
+        report
+    }
+
+    /// Writes the seeds of the soups that produced rare objects as CSV,
+    /// one line for each rare object and seed
+    pub fn rare_seeds(&self) -> String {
+        let mut seeds = String::from("code,name,seed\n");
+        for tally in self.rare_tallies() {
+            for seed in tally.seeds.iter() {
+                let _ = writeln!(
+                    seeds,
+                    "{},{},{}",
+                    tally.code,
+                    tally.name.unwrap_or_default(),
+                    seed
+                );
+            }
+        }
+        seeds
+    }
+
+    /// Adds the soups tallied by `other`
+    fn merge(&mut self, other: SoupSearchResults) {
+        for (code, tally) in other.tallies {
+            match self.tallies.get_mut(&code) {
+                Some(existing) => existing.merge(tally),
+                None => {
+                    self.tallies.insert(code, tally);
+                }
+            }
+        }
+        self.unidentified_objects += other.unidentified_objects;
+        self.unstable_soups += other.unstable_soups;
+        self.unstable_seeds.extend(other.unstable_seeds);
+        self.unstable_seeds.sort_unstable();
+        self.unstable_seeds.truncate(SOUP_SEARCH_SEEDS_KEPT);
+        self.generations += other.generations;
+    }
+
+    /// Tallies the objects `census` found in the soup drawn from `seed`
+    fn tally(&mut self, seed: u64, census: &Census) {
+        for entry in census.entries() {
+            let Some(code) = entry.code() else {
+                self.unidentified_objects += entry.count() as u64;
+                continue;
+            };
+
+            let tally = self
+                .tallies
+                .entry(code.to_string())
+                .or_insert_with(|| SoupSearchTally {
+                    code: code.to_string(),
+                    name: entry.name(),
+                    kind: match entry.kind() {
+                        ObjectKind::Spaceship {
+                            period,
+                            displacement: (rows, columns),
+                        } => ObjectKind::Spaceship {
+                            period,
+                            displacement: (
+                                rows.abs().max(columns.abs()),
+                                rows.abs().min(columns.abs()),
+                            ),
+                        },
+                        kind => kind,
+                    },
+                    objects: 0,
+                    soups: 0,
+                    seeds: vec![],
+                });
+            tally.objects += entry.count() as u64;
+            tally.soups += 1;
+            if tally.seeds.len() < SOUP_SEARCH_SEEDS_KEPT {
+                tally.seeds.push(seed);
+            }
+        }
+    }
+}
+
+/// Runs the soups described by `options` to stability and tallies the census
+/// of what they turned into. Soups are run on as many threads as asked for,
+/// each thread taking the next soup when done with one, and `progress` is
+/// called with the number of soups done after each of them
+pub fn search_soups<F>(options: SoupSearchOptions, progress: F) -> SoupSearchResults
+where
+    F: Fn(u64) + Sync,
+{
+    let started = Instant::now();
+    let next_soup = AtomicU64::new(0);
+    let done = AtomicU64::new(0);
+    let results = Mutex::new(SoupSearchResults {
+        options,
+        ..Default::default()
+    });
+
+    let threads = resolve_worker_threads(options.threads) as u64;
+    std::thread::scope(|scope| {
+        for _ in 0..threads.min(options.soups).max(1) {
+            scope.spawn(|| {
+                let mut found = SoupSearchResults::default();
+                loop {
+                    let index = next_soup.fetch_add(1, Ordering::Relaxed);
+                    if index >= options.soups {
+                        break;
+                    }
+
+                    let seed = options.first_seed.wrapping_add(index);
+                    let (census, generations) = run_soup(&options, seed);
+                    found.generations += generations;
+                    match census {
+                        Some(census) => found.tally(seed, &census),
+                        None => {
+                            found.unstable_soups += 1;
+                            if found.unstable_seeds.len() < SOUP_SEARCH_SEEDS_KEPT {
+                                found.unstable_seeds.push(seed);
+                            }
+                        }
+                    }
+                    progress(done.fetch_add(1, Ordering::Relaxed) + 1);
+                }
+                results.lock().unwrap().merge(found);
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.elapsed = started.elapsed();
+    results
+}
+
+/// Runs the soup drawn from `seed` until it stabilizes. Returns its census
+/// once stable, `None` when it did not stabilize or when the rule does not
+/// allow a census, along with the number of generations run
+fn run_soup(options: &SoupSearchOptions, seed: u64) -> (Option<Census>, u64) {
+    let mut universe = Universe::new_soup(options.side, options.side, options.soup(seed));
+    universe.set_rule(options.rule);
+    universe.set_topology(UniverseTopology::Unbounded);
+    universe.set_worker_threads(1);
+
+    let mut detector = StabilizationDetector::default();
+    let mut last_census: Option<Census> = None;
+    for generation in 0..=SOUP_SEARCH_MAX_GENERATIONS {
+        if detector
+            .observe(generation, universe.state_hash())
+            .is_some()
+        {
+            return (Census::of(&universe), generation);
+        }
+
+        if generation > 0 && generation % SOUP_SEARCH_CENSUS_INTERVAL == 0 {
+            let census = Census::of(&universe);
+            let identified = census
+                .as_ref()
+                .is_some_and(|census| census.entries().iter().all(|entry| entry.code().is_some()));
+            if identified && census == last_census {
+                return (census, generation);
+            }
+            last_census = census;
+        }
+
+        universe.tick();
+    }
+
+    (None, SOUP_SEARCH_MAX_GENERATIONS)
+}
+
+/// Describes `kind` in the report
+fn kind_name(kind: ObjectKind) -> String {
+    match kind {
+        ObjectKind::StillLife => String::from("still life"),
+        ObjectKind::Oscillator { period } => format!("p{period}"),
+        ObjectKind::Spaceship {
+            period,
+            displacement,
+        } => format!(
+            "{} ship",
+            Motion::new(period, displacement)
+                .speed()
+                .unwrap_or_default()
+        ),
+        ObjectKind::Unidentified => String::from("unknown"),
+    }
+}
+
+/// Writes `seeds` separated by commas between `prefix` and `suffix`,
+/// nothing when there is no seed
+fn seeds_list(seeds: &[u64], prefix: &str, suffix: &str) -> String {
+    if seeds.is_empty() {
+        return String::new();
+    }
+
+    let seeds: Vec<String> = seeds.iter().map(u64::to_string).collect();
+    format!("{prefix}{}{suffix}", seeds.join(", "))
+}
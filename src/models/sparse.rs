@@ -0,0 +1,294 @@
+use super::bitgrid::next_word;
+use super::parallel::{for_each_row_band, WorkerPool};
+use super::UniverseRule;
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// Side of the square tiles the plane is made of
+const TILE_SIDE: i64 = 64;
+
+/// The rows of a tile, bit n of each row being its n-th column
+type Tile = [u64; TILE_SIDE as usize];
+
+const EMPTY_TILE: Tile = [0; TILE_SIDE as usize];
+
+/// The living cells of an unbounded plane, stored as tiles of 64x64 cells.
+/// Only tiles holding at least one living cell are kept, so the plane
+/// grows and shrinks along with the patterns living on it
+#[derive(Debug, Clone, Default)]
+pub struct SparsePlane {
+    /// Tiles by (row, column) of the tile, tile 0x0 starting at cell 0x0
+    tiles: HashMap<(i64, i64), Tile>,
+
+    /// Tiles computed by the next `step`, and their next state
+    candidates: Vec<(i64, i64)>,
+    seen: HashSet<(i64, i64)>,
+    next: Vec<Tile>,
+}
+
+impl SparsePlane {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets roughly how many bytes the tiles of this plane take up
+    pub fn heap_size(&self) -> usize {
+        self.tiles.len() * std::mem::size_of::<((i64, i64), Tile)>()
+            + self.next.len() * std::mem::size_of::<Tile>()
+    }
+
+    /// Whether `rule` can run on an unbounded plane. Rules where cells with no
+    /// living neighbours are born would fill the whole plane in a single generation
+    pub fn supports(rule: &UniverseRule) -> bool {
+        rule.is_life_like() && rule.states() == 2 && !rule.is_birth(0)
+    }
+
+    /// Gets the tile holding the cell at `row`x`column`, along
+    /// with the row and the column of the cell within it
+    fn locate(row: i64, column: i64) -> ((i64, i64), usize, usize) {
+        (
+            (row.div_euclid(TILE_SIDE), column.div_euclid(TILE_SIDE)),
+            row.rem_euclid(TILE_SIDE) as usize,
+            column.rem_euclid(TILE_SIDE) as usize,
+        )
+    }
+
+    pub fn get(&self, row: i64, column: i64) -> bool {
+        let (key, row, column) = Self::locate(row, column);
+        self.tiles
+            .get(&key)
+            .is_some_and(|tile| tile[row] >> column & 1 == 1)
+    }
+
+    pub fn set(&mut self, row: i64, column: i64, alive: bool) {
+        let (key, row, column) = Self::locate(row, column);
+        if alive {
+            self.tiles.entry(key).or_insert(EMPTY_TILE)[row] |= 1 << column;
+        } else if let Some(tile) = self.tiles.get_mut(&key) {
+            tile[row] &= !(1 << column);
+            if tile.iter().all(|word| *word == 0) {
+                self.tiles.remove(&key);
+            }
+        }
+    }
+
+    /// Counts the living cells on the whole plane
+    pub fn population(&self) -> usize {
+        self.tiles
+            .values()
+            .flat_map(|tile| tile.iter())
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Gets the (top, left, bottom, right) rectangle holding every living cell
+    pub fn bounds(&self) -> Option<(i64, i64, i64, i64)> {
+        let mut bounds: Option<(i64, i64, i64, i64)> = None;
+        for (&(tile_row, tile_column), tile) in self.tiles.iter() {
+            let (Some(first), Some(last)) = (
+                tile.iter().position(|word| *word != 0),
+                tile.iter().rposition(|word| *word != 0),
+            ) else {
+                continue;
+            };
+            let columns = tile.iter().fold(0, |columns, word| columns | word);
+            let (row, column) = (tile_row * TILE_SIDE, tile_column * TILE_SIDE);
+            let tile_bounds = (
+                row + first as i64,
+                column + columns.trailing_zeros() as i64,
+                row + last as i64,
+                column + (TILE_SIDE - 1) - columns.leading_zeros() as i64,
+            );
+
+            bounds = Some(match bounds {
+                Some((top, left, bottom, right)) => (
+                    top.min(tile_bounds.0),
+                    left.min(tile_bounds.1),
+                    bottom.max(tile_bounds.2),
+                    right.max(tile_bounds.3),
+                ),
+                None => tile_bounds,
+            });
+        }
+        bounds
+    }
+
+    /// Counts the cells born and the cells that died going
+    /// from `previous` to this plane, as (births, deaths)
+    pub fn changes_from(&self, previous: &SparsePlane) -> (usize, usize) {
+        let (mut births, mut deaths) = (0, 0);
+        for (key, tile) in self.tiles.iter() {
+            let previous_tile = previous.tiles.get(key).unwrap_or(&EMPTY_TILE);
+            for (word, previous_word) in tile.iter().zip(previous_tile.iter()) {
+                births += (word & !previous_word).count_ones() as usize;
+                deaths += (previous_word & !word).count_ones() as usize;
+            }
+        }
+
+        // Tiles where every cell died are gone from this plane
+        for (key, previous_tile) in previous.tiles.iter() {
+            if !self.tiles.contains_key(key) {
+                deaths += previous_tile
+                    .iter()
+                    .map(|word| word.count_ones() as usize)
+                    .sum::<usize>();
+            }
+        }
+        (births, deaths)
+    }
+
+    /// Gets a hash of the living cells of the whole plane, whatever
+    /// the order its tiles are stored in
+    pub fn state_hash(&self) -> u64 {
+        self.tiles
+            .iter()
+            .map(|(key, tile)| {
+                let mut hasher = DefaultHasher::new();
+                key.hash(&mut hasher);
+                tile.hash(&mut hasher);
+                hasher.finish()
+            })
+            .fold(0, u64::wrapping_add)
+    }
+
+    /// Calls `f` with the plane coordinates of every living cell
+    /// in the `rows`x`columns` rectangle with its top left corner at `top`x`left`
+    pub fn for_each_alive_in<F: FnMut(i64, i64)>(
+        &self,
+        top: i64,
+        left: i64,
+        rows: i64,
+        columns: i64,
+        mut f: F,
+    ) {
+        let (bottom, right) = (top + rows, left + columns);
+        for (&(tile_row, tile_column), tile) in self.tiles.iter() {
+            let (tile_top, tile_left) = (tile_row * TILE_SIDE, tile_column * TILE_SIDE);
+            if tile_top >= bottom
+                || tile_left >= right
+                || tile_top + TILE_SIDE <= top
+                || tile_left + TILE_SIDE <= left
+            {
+                continue;
+            }
+
+            for (offset, word) in tile.iter().enumerate() {
+                let row = tile_top + offset as i64;
+                if !(top..bottom).contains(&row) {
+                    continue;
+                }
+
+                let mut word = *word;
+                while word != 0 {
+                    let column = tile_left + word.trailing_zeros() as i64;
+                    if (left..right).contains(&column) {
+                        f(row, column);
+                    }
+                    word &= word - 1;
+                }
+            }
+        }
+    }
+
+    /// Calls `f` with the plane coordinates of every living cell
+    pub fn for_each_alive<F: FnMut(i64, i64)>(&self, mut f: F) {
+        for (&(tile_row, tile_column), tile) in self.tiles.iter() {
+            for (offset, word) in tile.iter().enumerate() {
+                let mut word = *word;
+                while word != 0 {
+                    f(
+                        tile_row * TILE_SIDE + offset as i64,
+                        tile_column * TILE_SIDE + word.trailing_zeros() as i64,
+                    );
+                    word &= word - 1;
+                }
+            }
+        }
+    }
+
+    /// Computes the next generation of a two states rule on the Moore
+    /// neighbourhood. `birth` and `survival` have the n-th bit set when
+    /// n living neighbours make a cell be born or survive, and `birth`
+    /// must not have the zero bit set, or the whole plane would come to life.
+    /// Only tiles holding living cells and the tiles around them are computed,
+    /// on the threads of `workers`
+    pub fn step(&mut self, birth: u16, survival: u16, workers: &WorkerPool) {
+        debug_assert!(birth & 1 == 0, "B0 rules can not run on an unbounded plane");
+
+        let mut candidates = std::mem::take(&mut self.candidates);
+        let mut next = std::mem::take(&mut self.next);
+        candidates.clear();
+        self.seen.clear();
+        for (tile_row, tile_column) in self.tiles.keys() {
+            for delta_row in -1..=1 {
+                for delta_column in -1..=1 {
+                    let key = (tile_row + delta_row, tile_column + delta_column);
+                    if self.seen.insert(key) {
+                        candidates.push(key);
+                    }
+                }
+            }
+        }
+
+        let tile = |key: (i64, i64)| self.tiles.get(&key).unwrap_or(&EMPTY_TILE);
+        next.clear();
+        next.resize(candidates.len(), EMPTY_TILE);
+        for_each_row_band(&mut next, 1, workers, |range, next| {
+            for (index, next_tile) in range.zip(next.iter_mut()) {
+                let (tile_row, tile_column) = candidates[index];
+
+                // The tiles around this one, row by row from the top left one
+                let around: [&Tile; 9] = std::array::from_fn(|around| {
+                    let (delta_row, delta_column) = (around as i64 / 3 - 1, around as i64 % 3 - 1);
+                    tile((tile_row + delta_row, tile_column + delta_column))
+                });
+
+                // The west, the middle and the east words of a row
+                // of this tile, which may lie on the tiles above or below
+                let row_words = |row: isize| -> [u64; 3] {
+                    let (band, row) = match row {
+                        -1 => (0, TILE_SIDE as usize - 1),
+                        row if row == TILE_SIDE as isize => (2, 0),
+                        row => (1, row as usize),
+                    };
+                    [
+                        around[band * 3][row],
+                        around[band * 3 + 1][row],
+                        around[band * 3 + 2][row],
+                    ]
+                };
+                let west_of =
+                    |[west, middle, _]: [u64; 3]| (middle << 1) | (west >> (TILE_SIDE - 1));
+                let east_of =
+                    |[_, middle, east]: [u64; 3]| (middle >> 1) | (east << (TILE_SIDE - 1));
+
+                for (row, next_word_ref) in next_tile.iter_mut().enumerate() {
+                    let row = row as isize;
+                    let (above, current, below) =
+                        (row_words(row - 1), row_words(row), row_words(row + 1));
+                    let neighbours = [
+                        west_of(above),
+                        above[1],
+                        east_of(above),
+                        west_of(current),
+                        east_of(current),
+                        west_of(below),
+                        below[1],
+                        east_of(below),
+                    ];
+                    *next_word_ref = next_word(neighbours, current[1], birth, survival);
+                }
+            }
+        });
+
+        for (key, tile) in candidates.iter().zip(next.iter()) {
+            if tile.iter().any(|word| *word != 0) {
+                self.tiles.insert(*key, *tile);
+            } else {
+                self.tiles.remove(key);
+            }
+        }
+        self.candidates = candidates;
+        self.next = next;
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::{HashMap, VecDeque};
+
+/// The longest period end states are detected with
+const MAX_PERIOD: usize = 1000;
+
+/// How a universe ended up: going through the same states every
+/// `period` generations, from `generation` on. A period of one
+/// means the universe does not change anymore
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stabilization {
+    period: u64,
+    generation: u64,
+}
+
+impl Stabilization {
+    pub fn period(&self) -> u64 {
+        self.period
+    }
+
+    /// Gets the first generation of the repeating states
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Whether the universe does not change anymore
+    pub fn is_static(&self) -> bool {
+        self.period == 1
+    }
+}
+
+/// Detects when a universe stops changing or starts repeating itself,
+/// out of the hashes of its successive generations
+#[derive(Debug, Default, Clone)]
+pub struct StabilizationDetector {
+    /// The generation each recent hash was seen at
+    seen: HashMap<u64, u64>,
+
+    /// Recent hashes, the oldest first, forgotten past the longest period detected
+    recent: VecDeque<u64>,
+
+    last_generation: Option<u64>,
+}
+
+impl StabilizationDetector {
+    /// Forgets every generation observed
+    pub fn reset(&mut self) {
+        self.seen.clear();
+        self.recent.clear();
+        self.last_generation = None;
+    }
+
+    /// Observes that the states of `generation` hash to `hash`. Returns how the
+    /// universe stabilized when the same hash was seen at a previous generation.
+    /// A generation not following the last one observed starts over
+    pub fn observe(&mut self, generation: u64, hash: u64) -> Option<Stabilization> {
+        if self
+            .last_generation
+            .is_some_and(|last| last.checked_add(1) != Some(generation))
+        {
+            self.reset();
+        }
+        self.last_generation = Some(generation);
+
+        if let Some(seen) = self.seen.get(&hash) {
+            return Some(Stabilization {
+                period: generation - seen,
+                generation: *seen,
+            });
+        }
+
+        self.seen.insert(hash, generation);
+        self.recent.push_back(hash);
+        if self.recent.len() > MAX_PERIOD {
+            if let Some(oldest) = self.recent.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CellularAutomaton, Universe, UniverseCell};
+
+    const BLINKER: [(usize, usize); 3] = [(4, 3), (4, 4), (4, 5)];
+    const BLOCK: [(usize, usize); 4] = [(3, 3), (3, 4), (4, 3), (4, 4)];
+
+    /// Runs a universe holding `cells` until it is found to have stabilized
+    fn stabilization_of(cells: &[(usize, usize)]) -> Option<Stabilization> {
+        let mut universe = Universe::new(10, 10);
+        for (row, column) in cells {
+            universe.set_cell(*row, *column, UniverseCell::Alive);
+        }
+
+        let mut detector = StabilizationDetector::default();
+        for _ in 0..10 {
+            let stabilization = detector.observe(universe.generation(), universe.state_hash());
+            if stabilization.is_some() {
+                return stabilization;
+            }
+            universe.tick();
+        }
+        None
+    }
+
+    #[test]
+    fn blinker_has_period_two() {
+        let stabilization = stabilization_of(&BLINKER).unwrap();
+        assert_eq!(stabilization.period(), 2);
+        assert_eq!(stabilization.generation(), 0);
+        assert!(!stabilization.is_static());
+    }
+
+    #[test]
+    fn block_has_period_one() {
+        let stabilization = stabilization_of(&BLOCK).unwrap();
+        assert_eq!(stabilization.period(), 1);
+        assert!(stabilization.is_static());
+    }
+}
@@ -0,0 +1,306 @@
+use super::CellularAutomaton;
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::path::Path;
+
+/// The number of generations statistics are recorded for
+pub const STATISTICS_MAX_GENERATIONS: usize = 100_000;
+
+/// Figures about a generation of an automaton
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenerationStatistics {
+    generation: u64,
+    population: usize,
+
+    /// Cells born since the previous generation, when it is known
+    births: Option<usize>,
+
+    /// Cells that died since the previous generation, when it is known
+    deaths: Option<usize>,
+
+    /// The number of cells the population lives in: the whole universe,
+    /// or the bounding box of the living cells of unbounded universes
+    area: usize,
+
+    /// The (top, left, bottom, right) rectangle holding every living cell
+    bounding_box: Option<(i64, i64, i64, i64)>,
+}
+
+impl GenerationStatistics {
+    /// Computes the statistics of `automaton`, `previous` being
+    /// the automaton it was computed from, if there is one
+    pub fn of(automaton: &dyn CellularAutomaton, previous: Option<&dyn CellularAutomaton>) -> Self {
+        let bounding_box = automaton.bounding_box();
+        let universe = automaton.as_universe();
+        let changes = universe
+            .zip(previous.and_then(|previous| previous.as_universe()))
+            .and_then(|(universe, previous)| universe.changes_from(previous));
+        let area = if universe.is_some_and(|universe| universe.topology().is_unbounded()) {
+            bounding_box.map_or(0, |(top, left, bottom, right)| {
+                ((bottom - top + 1) * (right - left + 1)) as usize
+            })
+        } else {
+            automaton.rows() * automaton.columns()
+        };
+
+        Self {
+            generation: automaton.generation(),
+            population: automaton.population(),
+            births: changes.map(|(births, _)| births),
+            deaths: changes.map(|(_, deaths)| deaths),
+            area,
+            bounding_box,
+        }
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn population(&self) -> usize {
+        self.population
+    }
+
+    pub fn births(&self) -> Option<usize> {
+        self.births
+    }
+
+    pub fn deaths(&self) -> Option<usize> {
+        self.deaths
+    }
+
+    /// Gets the share of the cells the population lives in that are alive, from 0 to 1
+    pub fn density(&self) -> f64 {
+        if self.area == 0 {
+            0.0
+        } else {
+            self.population as f64 / self.area as f64
+        }
+    }
+
+    /// Gets the (top, left, bottom, right) rectangle holding every living cell
+    pub fn bounding_box(&self) -> Option<(i64, i64, i64, i64)> {
+        self.bounding_box
+    }
+
+    /// Gets the number of rows and columns of the bounding box of the living cells
+    pub fn bounding_box_size(&self) -> Option<(usize, usize)> {
+        self.bounding_box.map(|(top, left, bottom, right)| {
+            ((bottom - top + 1) as usize, (right - left + 1) as usize)
+        })
+    }
+}
+
+/// The formats statistics are exported to
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StatisticsFormat {
+    /// Comma separated values, one line per generation after a header line
+    #[default]
+    Csv,
+
+    /// An array of objects, one per generation
+    Json,
+}
+
+impl StatisticsFormat {
+    /// Gets the format matching the extension of `path`, CSV unless it is `.json`
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => StatisticsFormat::Json,
+            _ => StatisticsFormat::Csv,
+        }
+    }
+}
+
+/// The statistics of the successive generations of an automaton,
+/// the oldest generations being forgotten past `STATISTICS_MAX_GENERATIONS`
+#[derive(Debug, Clone, Default)]
+pub struct StatisticsRecord {
+    /// Statistics by increasing generation
+    entries: VecDeque<GenerationStatistics>,
+}
+
+impl StatisticsRecord {
+    /// Records `statistics`, forgetting the ones of the same generation or of
+    /// later ones, which the automaton will not go through anymore
+    pub fn record(&mut self, statistics: GenerationStatistics) {
+        while self
+            .entries
+            .back()
+            .is_some_and(|last| last.generation >= statistics.generation)
+        {
+            self.entries.pop_back();
+        }
+
+        self.entries.push_back(statistics);
+        if self.entries.len() > STATISTICS_MAX_GENERATIONS {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Gets the statistics recorded for `generation`
+    pub fn get(&self, generation: u64) -> Option<&GenerationStatistics> {
+        self.entries
+            .binary_search_by_key(&generation, |entry| entry.generation)
+            .ok()
+            .map(|index| &self.entries[index])
+    }
+
+    /// Gets the generation and the population of, at most, the
+    /// last `count` generations recorded up to `generation`
+    pub fn population_series(&self, generation: u64, count: usize) -> Vec<(u64, usize)> {
+        let end = self
+            .entries
+            .partition_point(|entry| entry.generation <= generation);
+        self.entries
+            .range(end.saturating_sub(count)..end)
+            .map(|entry| (entry.generation, entry.population))
+            .collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &GenerationStatistics> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Writes the statistics of every generation recorded in `format`. Births
+    /// and deaths that are not known, and the bounding box of generations
+    /// with no living cell, are left empty in CSV and are null in JSON
+    pub fn export(&self, format: StatisticsFormat) -> String {
+        let mut exported = String::new();
+        match format {
+            StatisticsFormat::Csv => {
+                exported.push_str(
+                    "generation,population,births,deaths,density,\
+                     bounding_box_top,bounding_box_left,bounding_box_rows,bounding_box_columns\n",
+                );
+                let optional = |value: Option<usize>| value.map(|value| value.to_string());
+                for entry in self.entries.iter() {
+                    let bounding_box = match (entry.bounding_box, entry.bounding_box_size()) {
+                        (Some((top, left, _, _)), Some((rows, columns))) => {
+                            format!("{top},{left},{rows},{columns}")
+                        }
+                        _ => String::from(",,,"),
+                    };
+                    let _ = writeln!(
+                        exported,
+                        "{},{},{},{},{},{}",
+                        entry.generation,
+                        entry.population,
+                        optional(entry.births).unwrap_or_default(),
+                        optional(entry.deaths).unwrap_or_default(),
+                        entry.density(),
+                        bounding_box
+                    );
+                }
+            }
+            StatisticsFormat::Json => {
+                let optional = |value: Option<usize>| {
+                    value.map_or_else(|| String::from("null"), |value| value.to_string())
+                };
+                exported.push('[');
+                for (index, entry) in self.entries.iter().enumerate() {
+                    let bounding_box = match (entry.bounding_box, entry.bounding_box_size()) {
+                        (Some((top, left, _, _)), Some((rows, columns))) => format!(
+                            "{{\"top\":{top},\"left\":{left},\"rows\":{rows},\"columns\":{columns}}}"
+                        ),
+                        _ => String::from("null"),
+                    };
+                    let _ = write!(
+                        exported,
+                        "{}\n  {{\"generation\":{},\"population\":{},\"births\":{},\"deaths\":{},\"density\":{},\"bounding_box\":{}}}",
+                        if index == 0 { "" } else { "," },
+                        entry.generation,
+                        entry.population,
+                        optional(entry.births),
+                        optional(entry.deaths),
+                        entry.density(),
+                        bounding_box
+                    );
+                }
+                exported.push_str("\n]\n");
+            }
+        }
+        exported
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Universe, UniverseCell};
+
+    /// Records a blinker for two generations, then an empty universe
+    fn blinker_record() -> StatisticsRecord {
+        let mut universe = Universe::new(8, 8);
+        for column in 2..5 {
+            universe.set_cell(3, column, UniverseCell::Alive);
+        }
+
+        let mut record = StatisticsRecord::default();
+        record.record(GenerationStatistics::of(&universe, None));
+        let previous = universe.clone();
+        universe.tick();
+        record.record(GenerationStatistics::of(&universe, Some(&previous)));
+        let mut empty = Universe::new(8, 8);
+        for _ in 0..2 {
+            empty.tick();
+        }
+        record.record(GenerationStatistics::of(&empty, None));
+        record
+    }
+
+    #[test]
+    fn statistics_export_as_csv() {
+        assert_eq!(
+            blinker_record().export(StatisticsFormat::Csv),
+            "generation,population,births,deaths,density,\
+             bounding_box_top,bounding_box_left,bounding_box_rows,bounding_box_columns\n\
+             0,3,,,0.046875,3,2,1,3\n\
+             1,3,2,2,0.046875,2,3,3,1\n\
+             2,0,,,0,,,,\n"
+        );
+    }
+
+    #[test]
+    fn statistics_export_as_json() {
+        assert_eq!(
+            blinker_record().export(StatisticsFormat::Json),
+            "[\n  \
+             {\"generation\":0,\"population\":3,\"births\":null,\"deaths\":null,\"density\":0.046875,\
+             \"bounding_box\":{\"top\":3,\"left\":2,\"rows\":1,\"columns\":3}},\n  \
+             {\"generation\":1,\"population\":3,\"births\":2,\"deaths\":2,\"density\":0.046875,\
+             \"bounding_box\":{\"top\":2,\"left\":3,\"rows\":3,\"columns\":1}},\n  \
+             {\"generation\":2,\"population\":0,\"births\":null,\"deaths\":null,\"density\":0,\
+             \"bounding_box\":null}\n]\n"
+        );
+    }
+
+    #[test]
+    fn recording_an_earlier_generation_forgets_later_ones() {
+        let mut record = blinker_record();
+        let first = *record.get(0).unwrap();
+        record.record(first);
+        assert_eq!(record.len(), 1);
+        assert_eq!(record.population_series(10, 5), vec![(0, 3)]);
+    }
+
+    #[test]
+    fn formats_follow_the_extension() {
+        for (path, format) in [
+            ("statistics.json", StatisticsFormat::Json),
+            ("statistics.JSON", StatisticsFormat::Json),
+            ("statistics.csv", StatisticsFormat::Csv),
+            ("statistics", StatisticsFormat::Csv),
+        ] {
+            assert_eq!(StatisticsFormat::from_path(Path::new(path)), format);
+        }
+    }
+}
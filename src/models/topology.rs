@@ -0,0 +1,290 @@
+use super::sparse::SparsePlane;
+use super::{UniverseRule, UNIVERSE_RULE_MAX_RANGE};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Describes how the edges of a universe are glued together, and therefore
+/// which cells are found past them
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UniverseTopology {
+    /// No wrapping at all. Everything past the edges is dead
+    DeadBoundary,
+
+    /// Left and right edges are joined, as well as top and bottom ones
+    #[default]
+    Torus,
+
+    /// Left and right edges are joined, top and bottom ones are dead
+    HorizontalCylinder,
+
+    /// Top and bottom edges are joined, left and right ones are dead
+    VerticalCylinder,
+
+    /// Left and right edges are joined, top and bottom ones are joined
+    /// with a twist, mirroring columns
+    KleinBottle,
+
+    /// Both pairs of edges are joined with a twist, mirroring rows
+    /// and columns (a real projective plane)
+    CrossSurface,
+
+    /// No edges at all: the universe is a viewport onto an unbounded plane
+    Unbounded,
+}
+
+impl UniverseTopology {
+    /// All the topologies, in the order they are offered to the user
+    pub const ALL: [UniverseTopology; 7] = [
+        UniverseTopology::DeadBoundary,
+        UniverseTopology::Torus,
+        UniverseTopology::HorizontalCylinder,
+        UniverseTopology::VerticalCylinder,
+        UniverseTopology::KleinBottle,
+        UniverseTopology::CrossSurface,
+        UniverseTopology::Unbounded,
+    ];
+
+    fn wraps_columns(&self) -> bool {
+        !matches!(
+            self,
+            UniverseTopology::DeadBoundary
+                | UniverseTopology::VerticalCylinder
+                | UniverseTopology::Unbounded
+        )
+    }
+
+    fn wraps_rows(&self) -> bool {
+        !matches!(
+            self,
+            UniverseTopology::DeadBoundary
+                | UniverseTopology::HorizontalCylinder
+                | UniverseTopology::Unbounded
+        )
+    }
+
+    /// Gets the topology gluing the edges of a universe the same way once its
+    /// rows became columns. Cylinders have their joined edges swapped.
+    /// `None` for Klein bottles, whose twist would have to move to their
+    /// left and right edges, which no topology glues that way
+    pub fn transposed(&self) -> Option<Self> {
+        match self {
+            UniverseTopology::HorizontalCylinder => Some(UniverseTopology::VerticalCylinder),
+            UniverseTopology::VerticalCylinder => Some(UniverseTopology::HorizontalCylinder),
+            UniverseTopology::KleinBottle => None,
+            other => Some(*other),
+        }
+    }
+
+    /// Gets the widest neighbourhood range rules can have on a `rows`x`columns`
+    /// universe. Past it, neighbourhoods wrapping around joined edges would
+    /// reach some cells more than once
+    pub fn max_range(&self, rows: usize, columns: usize) -> u8 {
+        let fitting = |cells: usize, wraps: bool| {
+            if wraps {
+                (cells.saturating_sub(1) / 2).min(UNIVERSE_RULE_MAX_RANGE as usize) as u8
+            } else {
+                UNIVERSE_RULE_MAX_RANGE
+            }
+        };
+        fitting(rows, self.wraps_rows())
+            .min(fitting(columns, self.wraps_columns()))
+            .max(1)
+    }
+
+    /// Whether universes with this topology can evolve with `rule`. Unbounded
+    /// planes only take two states Life-like rules without births out of
+    /// nothing, the only ones their sparse tiles can compute
+    pub fn supports(&self, rule: &UniverseRule) -> bool {
+        !self.is_unbounded() || SparsePlane::supports(rule)
+    }
+
+    /// Whether cells live on an unbounded plane rather than on the universe itself
+    pub fn is_unbounded(&self) -> bool {
+        matches!(self, UniverseTopology::Unbounded)
+    }
+
+    /// Maps a possibly out of bounds position onto a cell of a `rows`x`columns`
+    /// universe. Returns `None` when the position lies past a dead boundary,
+    /// or past the edges of the viewport of an unbounded universe
+    pub fn wrap(
+        &self,
+        row: isize,
+        column: isize,
+        rows: usize,
+        columns: usize,
+    ) -> Option<(usize, usize)> {
+        let (rows, columns) = (rows as isize, columns as isize);
+        let row_inside = (0..rows).contains(&row);
+        let column_inside = (0..columns).contains(&column);
+        if row_inside && column_inside {
+            return Some((row as usize, column as usize));
+        }
+
+        if (!row_inside && !self.wraps_rows()) || (!column_inside && !self.wraps_columns()) {
+            return None;
+        }
+
+        // Number of times each pair of edges is crossed
+        let row_turns = row.div_euclid(rows);
+        let column_turns = column.div_euclid(columns);
+        let mut wrapped_row = row.rem_euclid(rows);
+        let mut wrapped_column = column.rem_euclid(columns);
+
+        match self {
+            UniverseTopology::KleinBottle if row_turns % 2 != 0 => {
+                wrapped_column = columns - 1 - wrapped_column;
+            }
+            UniverseTopology::CrossSurface => {
+                if row_turns % 2 != 0 {
+                    wrapped_column = columns - 1 - wrapped_column;
+                }
+                if column_turns % 2 != 0 {
+                    wrapped_row = rows - 1 - wrapped_row;
+                }
+            }
+            _ => (),
+        }
+
+        Some((wrapped_row as usize, wrapped_column as usize))
+    }
+}
+
+impl fmt::Display for UniverseTopology {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            UniverseTopology::DeadBoundary => "dead-boundary",
+            UniverseTopology::Torus => "torus",
+            UniverseTopology::HorizontalCylinder => "horizontal-cylinder",
+            UniverseTopology::VerticalCylinder => "vertical-cylinder",
+            UniverseTopology::KleinBottle => "klein-bottle",
+            UniverseTopology::CrossSurface => "cross-surface",
+            UniverseTopology::Unbounded => "unbounded",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownTopologyError;
+
+impl fmt::Display for UnknownTopologyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown universe topology")
+    }
+}
+
+impl std::error::Error for UnknownTopologyError {}
+
+impl FromStr for UniverseTopology {
+    type Err = UnknownTopologyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        UniverseTopology::ALL
+            .into_iter()
+            .find(|topology| topology.to_string() == s)
+            .ok_or(UnknownTopologyError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROWS: usize = 4;
+    const COLUMNS: usize = 5;
+
+    fn wrap(topology: UniverseTopology, row: isize, column: isize) -> Option<(usize, usize)> {
+        topology.wrap(row, column, ROWS, COLUMNS)
+    }
+
+    #[test]
+    fn klein_bottles_mirror_columns_across_top_and_bottom() {
+        let klein = UniverseTopology::KleinBottle;
+        assert_eq!(wrap(klein, 2, 3), Some((2, 3)));
+        assert_eq!(wrap(klein, -1, 1), Some((3, 3)));
+        assert_eq!(wrap(klein, 4, 0), Some((0, 4)));
+        assert_eq!(wrap(klein, 1, -1), Some((1, 4)));
+        assert_eq!(wrap(klein, 1, 5), Some((1, 0)));
+        assert_eq!(wrap(klein, -1, -1), Some((3, 0)));
+        // Crossing twice undoes the twist
+        assert_eq!(wrap(klein, 9, 1), Some((1, 1)));
+    }
+
+    #[test]
+    fn cross_surfaces_mirror_across_every_edge() {
+        let cross = UniverseTopology::CrossSurface;
+        assert_eq!(wrap(cross, 2, 3), Some((2, 3)));
+        assert_eq!(wrap(cross, -1, 1), Some((3, 3)));
+        assert_eq!(wrap(cross, 4, 0), Some((0, 4)));
+        assert_eq!(wrap(cross, 1, -1), Some((2, 4)));
+        assert_eq!(wrap(cross, 0, 5), Some((3, 0)));
+        assert_eq!(wrap(cross, -1, -1), Some((0, 0)));
+        assert_eq!(wrap(cross, 1, 11), Some((1, 1)));
+    }
+
+    #[test]
+    fn dead_edges_have_nothing_past_them() {
+        let past_edges = [(-1, 2), (ROWS as isize, 2), (1, -1), (1, COLUMNS as isize)];
+        for (topology, wrapped) in [
+            (UniverseTopology::DeadBoundary, [false, false, false, false]),
+            (UniverseTopology::Unbounded, [false, false, false, false]),
+            (
+                UniverseTopology::HorizontalCylinder,
+                [false, false, true, true],
+            ),
+            (
+                UniverseTopology::VerticalCylinder,
+                [true, true, false, false],
+            ),
+            (UniverseTopology::Torus, [true, true, true, true]),
+        ] {
+            for ((row, column), wraps) in past_edges.into_iter().zip(wrapped) {
+                assert_eq!(
+                    wrap(topology, row, column).is_some(),
+                    wraps,
+                    "{topology} at {row}x{column}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn neighbours_are_mutual() {
+        let around: Vec<(isize, isize)> = (-1..=1)
+            .flat_map(|row| (-1..=1).map(move |column| (row, column)))
+            .collect();
+        for topology in UniverseTopology::ALL {
+            for row in 0..ROWS as isize {
+                for column in 0..COLUMNS as isize {
+                    for (delta_row, delta_column) in around.iter() {
+                        let Some((other_row, other_column)) =
+                            wrap(topology, row + delta_row, column + delta_column)
+                        else {
+                            continue;
+                        };
+                        let back = around.iter().any(|(delta_row, delta_column)| {
+                            wrap(
+                                topology,
+                                other_row as isize + delta_row,
+                                other_column as isize + delta_column,
+                            ) == Some((row as usize, column as usize))
+                        });
+                        assert!(back, "{topology} at {row}x{column}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for topology in UniverseTopology::ALL {
+            assert_eq!(topology.to_string().parse(), Ok(topology));
+        }
+        assert_eq!(
+            "moebius".parse::<UniverseTopology>(),
+            Err(UnknownTopologyError)
+        );
+    }
+}
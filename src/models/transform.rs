@@ -0,0 +1,101 @@
+use super::UniverseNeighbourhood;
+
+/// A rearrangement of all of the cells of a universe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UniverseTransform {
+    /// A quarter turn clockwise
+    RotateClockwise,
+
+    /// A half turn
+    RotateHalfTurn,
+
+    /// A quarter turn counterclockwise
+    RotateCounterclockwise,
+
+    /// Mirrors columns, left becoming right
+    FlipHorizontally,
+
+    /// Mirrors rows, top becoming bottom
+    FlipVertically,
+
+    /// Mirrors along the diagonal from the top left corner, rows becoming columns
+    Transpose,
+}
+
+impl UniverseTransform {
+    /// Whether rows become columns, and columns become rows
+    pub fn swaps_axes(&self) -> bool {
+        matches!(
+            self,
+            UniverseTransform::RotateClockwise
+                | UniverseTransform::RotateCounterclockwise
+                | UniverseTransform::Transpose
+        )
+    }
+
+    /// Gets the rows and the columns of a `rows`x`columns` universe once transformed
+    pub fn dimensions(&self, rows: usize, columns: usize) -> (usize, usize) {
+        if self.swaps_axes() {
+            (columns, rows)
+        } else {
+            (rows, columns)
+        }
+    }
+
+    /// Gets where the cell at `row`x`column` of a `rows`x`columns` universe goes.
+    /// Positions outside of the universe, such as the ones of unbounded
+    /// universes past their viewport, are moved along with it
+    pub fn place(&self, row: i64, column: i64, rows: i64, columns: i64) -> (i64, i64) {
+        let (last_row, last_column) = (rows - 1, columns - 1);
+        match self {
+            UniverseTransform::RotateClockwise => (column, last_row - row),
+            UniverseTransform::RotateHalfTurn => (last_row - row, last_column - column),
+            UniverseTransform::RotateCounterclockwise => (last_column - column, row),
+            UniverseTransform::FlipHorizontally => (row, last_column - column),
+            UniverseTransform::FlipVertically => (last_row - row, column),
+            UniverseTransform::Transpose => (column, row),
+        }
+    }
+
+    /// Whether the cells of a `rows`x`columns` grid laid out for `neighbourhood`
+    /// keep the same neighbours once transformed. Square grids always do.
+    /// Shapes of hexagonal and triangular cells depend on the parity of their
+    /// row and column, which some transforms do not keep, and are never
+    /// the same once turned by a quarter
+    pub fn preserves(
+        &self,
+        neighbourhood: UniverseNeighbourhood,
+        rows: usize,
+        columns: usize,
+    ) -> bool {
+        let (rows, columns) = (rows as i64, columns as i64);
+        let offsets = |row: i64, column: i64| {
+            neighbourhood.grid_offsets(row.rem_euclid(2) as usize, column.rem_euclid(2) as usize)
+        };
+
+        // Checking a cell of each parity is enough, offsets only depending on it
+        (0..2).all(|row| {
+            (0..2).all(|column| {
+                let Some(neighbours) = offsets(row, column) else {
+                    return true;
+                };
+                let (placed_row, placed_column) = self.place(row, column, rows, columns);
+                let Some(placed_neighbours) = offsets(placed_row, placed_column) else {
+                    return false;
+                };
+                neighbours.iter().all(|(delta_row, delta_column)| {
+                    let (neighbour_row, neighbour_column) = self.place(
+                        row + *delta_row as i64,
+                        column + *delta_column as i64,
+                        rows,
+                        columns,
+                    );
+                    placed_neighbours.contains(&(
+                        (neighbour_row - placed_row) as isize,
+                        (neighbour_column - placed_column) as isize,
+                    ))
+                })
+            })
+        })
+    }
+}
@@ -1,12 +1,21 @@
-use super::{UniverseCell, UniversePoint, UniversePointMatrix};
+use super::bitgrid::BitGrid;
+use super::hashlife::HashLife;
+use super::parallel::{for_each_row_band, WorkerPool};
+use super::sparse::SparsePlane;
+use super::{
+    split_objects, AutomatonKind, CellularAutomaton, CorpseDecay, Motion, MotionError, ObjectCells,
+    ResizeAnchor, SoupOptions, StateColor, UniverseCell, UniverseNeighbourhood, UniversePoint,
+    UniversePointMatrix, UniverseRule, UniverseTopology, UniverseTransform,
+};
 use crate::config::G_LOG_DOMAIN;
-use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fmt;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicBool, Ordering};
 
-const UNIVERSE_RANDOM_ALIVE_PROBABILITY: f64 = 0.6;
-const UNIVERSE_CELL_INITIAL_CORPSE_HEAT: f64 = 0.65;
-const UNIVERSE_DEFAULT_FREEZE_RATE: f64 = 0.30;
+/// The number of rows and columns universes have at most
+pub const UNIVERSE_MAX_SIDE: usize = 2000;
 
 fn compute_initial_delta(universe: &mut Universe) {
     let mut initial_delta: Vec<UniversePoint> = vec![];
@@ -26,17 +35,97 @@ fn compute_initial_delta(universe: &mut Universe) {
     universe.last_delta = Some(initial_delta);
 }
 
+/// Grows the (top, left, bottom, right) rectangle `bounds` to hold the cell
+/// at `row`x`column`, starting from that cell alone when `bounds` is `None`
+fn extend_bounds<T: Ord + Copy>(bounds: &mut Option<(T, T, T, T)>, row: T, column: T) {
+    *bounds = Some(match *bounds {
+        Some((top, left, bottom, right)) => (
+            top.min(row),
+            left.min(column),
+            bottom.max(row),
+            right.max(column),
+        ),
+        None => (row, column, row, column),
+    });
+}
+
+/// Buffers reused from one generation to the next,
+/// so that computing a generation allocates nothing
+#[derive(Debug, Default)]
+struct TickBuffers {
+    /// Back buffer the next generation is written to, before
+    /// being swapped with the cells of the universe
+    cells: Vec<UniverseCell>,
+
+    /// Living neighbours of each cell
+    counts: Vec<u16>,
+
+    /// Living cells surrounded by the cells past the edges,
+    /// and the table of their partial sums
+    padded: Vec<u32>,
+    sums: Vec<u32>,
+
+    /// Cells whose state may change, and whether each cell is among them
+    active: Vec<usize>,
+    marked: Vec<bool>,
+
+    /// Next state of the active cells that change
+    changes: Vec<(usize, UniverseCell)>,
+}
+
+impl Clone for TickBuffers {
+    /// Buffers are only meaningful while computing a generation,
+    /// so copies start with empty ones
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
 /// Represents a universe as a collection of "cells"
-/// which can be in two states: `Alive` or `Dead`
+/// which can be `Alive`, `Dead` or, with multi-state rules, `Dying`.
+/// Cells evolve according to a Life-like `UniverseRule`, on a surface
+/// described by a `UniverseTopology`
 #[derive(Clone, Debug)]
 pub struct Universe {
     columns: usize,
     rows: usize,
     cells: Vec<UniverseCell>,
     death_map: Vec<f64>,
-    corpse_freeze_rate: f64,
+    rule: UniverseRule,
+    topology: UniverseTopology,
+
+    /// Living cells packed as bits, kept along with `cells` while
+    /// the universe evolves with two states rules on the Moore neighbourhood
+    bits: Option<BitGrid>,
+
+    /// The living cells of unbounded universes, which are seen through
+    /// a viewport of `rows`x`columns` cells
+    plane: Option<SparsePlane>,
+
+    /// Plane coordinates of the top left cell of the viewport
+    viewport_origin: (i64, i64),
+
+    /// How the universe was randomly filled when created, if it was
+    soup: Option<SoupOptions>,
+
+    /// Threads computing each generation
+    workers: WorkerPool,
+
+    /// How dead cells fade out
+    corpse_decay: CorpseDecay,
+
+    /// Cells with some heat left in the death map
+    warm_cells: Vec<usize>,
+
     generations: u64,
+
+    /// Cells changed by the last generation. `None` when they are not known
     last_delta: Option<Vec<UniversePoint>>,
+
+    /// Cells set since the last generation, as (row, column)
+    edited_cells: Vec<(usize, usize)>,
+
+    buffers: TickBuffers,
 }
 
 impl Default for Universe {
@@ -46,15 +135,21 @@ impl Default for Universe {
 }
 
 impl Universe {
-    pub fn new(width: usize, height: usize) -> Universe {
-        let mut universe = Self::create(width, height);
+    pub fn new(rows: usize, columns: usize) -> Universe {
+        let mut universe = Self::create(rows, columns);
         compute_initial_delta(&mut universe);
         universe
     }
 
+    /// Creates a universe filled with a soup drawn from a random seed
     pub fn new_random(rows: usize, columns: usize) -> Universe {
+        Self::new_soup(rows, columns, SoupOptions::random())
+    }
+
+    /// Creates a universe filled with the soup described by `soup`
+    pub fn new_soup(rows: usize, columns: usize, soup: SoupOptions) -> Universe {
         let mut universe = Self::create(rows, columns);
-        universe.random_seed();
+        universe.seed_soup(soup);
         compute_initial_delta(&mut universe);
         universe
     }
@@ -77,26 +172,34 @@ impl Universe {
             rows,
             columns,
             cells,
-            corpse_freeze_rate: UNIVERSE_DEFAULT_FREEZE_RATE,
+            rule: UniverseRule::default(),
+            topology: UniverseTopology::default(),
+            bits: None,
+            plane: None,
+            viewport_origin: (0, 0),
+            soup: None,
+            workers: WorkerPool::default(),
+            corpse_decay: CorpseDecay::default(),
+            warm_cells: vec![],
             death_map,
             generations: 0,
             last_delta: None,
+            edited_cells: vec![],
+            buffers: TickBuffers::default(),
         }
     }
 
-    /// Seeds this universe with random values
-    fn random_seed(&mut self) {
-        let mut rng = rand::thread_rng();
-        for i in 0..self.rows {
-            for j in 0..self.columns {
-                let y: f64 = rng.gen();
-                if y >= UNIVERSE_RANDOM_ALIVE_PROBABILITY {
-                    self.set_cell(i, j, UniverseCell::Alive);
-                } else {
-                    self.set_cell(i, j, UniverseCell::Dead);
-                }
-            }
-        }
+    /// Seeds this empty universe with the soup described by `soup`
+    fn seed_soup(&mut self, soup: SoupOptions) {
+        soup.fill(self.rows, self.columns, |row, column| {
+            self.set_cell(row, column, UniverseCell::Alive);
+        });
+        self.soup = Some(soup);
+    }
+
+    /// How this universe was randomly filled when created, if it was
+    pub fn soup(&self) -> Option<&SoupOptions> {
+        self.soup.as_ref()
     }
 
     #[allow(dead_code)]
@@ -112,6 +215,19 @@ impl Universe {
     pub fn set_cell(&mut self, row: usize, column: usize, cell: UniverseCell) {
         let i = self.get_index(row, column);
         self.cells[i] = cell;
+        if self.last_delta.is_some() {
+            self.edited_cells.push((row, column));
+        }
+        if let Some(bits) = self.bits.as_mut() {
+            bits.set(row, column, cell.is_alive());
+        }
+        if let Some(plane) = self.plane.as_mut() {
+            plane.set(
+                self.viewport_origin.0 + row as i64,
+                self.viewport_origin.1 + column as i64,
+                cell.is_alive(),
+            );
+        }
     }
 
     /// Gets the cell at `row`x`column`.
@@ -125,108 +241,1034 @@ impl Universe {
         }
     }
 
-    /// Computes the next state of a cell given its current state
-    /// and the state of its neighbours
-    fn cell_next_state(&self, row: usize, column: usize) -> UniverseCell {
-        let (cell, _) = self.get_cell(row, column);
-        let alive_cells_around = self.cell_living_neighbours_count(row, column);
+    /// Counts living adiacents cells for a given cell at `row`x`column` coordinates
+    fn cell_living_neighbours_count(&self, row: usize, column: usize) -> u16 {
+        let mut count = 0;
+        for delta_row in -1..=1 {
+            for delta_col in -1..=1 {
+                if delta_row == 0 && delta_col == 0 {
+                    continue;
+                }
 
-        match cell {
-            UniverseCell::Alive => {
-                if !(2..=3).contains(&alive_cells_around) {
-                    UniverseCell::Dead
-                } else {
-                    // This include the condition alive_cells_around == 2 || alive_cells_around == 3
-                    UniverseCell::Alive
+                if self.is_alive_at(row as isize + delta_row, column as isize + delta_col) {
+                    count += 1;
                 }
             }
+        }
+        count
+    }
+
+    /// Tells whether the cell at a possibly out of bounds position is alive,
+    /// following the topology of this universe past the edges
+    fn is_alive_at(&self, row: isize, column: isize) -> bool {
+        self.topology
+            .wrap(row, column, self.rows, self.columns)
+            .is_some_and(|(row, column)| self.cells[self.get_index(row, column)].is_alive())
+    }
+
+    /// Counts living adiacent cells for a given cell at `row`x`column` coordinates
+    /// on hexagonal and triangular grids, where neighbours depend on the cell position
+    fn cell_grid_neighbours_count(&self, row: usize, column: usize) -> u16 {
+        let offsets = self
+            .rule
+            .neighbourhood()
+            .grid_offsets(row, column)
+            .unwrap_or_default();
+
+        offsets
+            .iter()
+            .filter(|(delta_row, delta_column)| {
+                self.is_alive_at(row as isize + delta_row, column as isize + delta_column)
+            })
+            .count() as u16
+    }
+
+    /// Counts the living neighbours of the cell at `row`x`column`,
+    /// according to the neighbourhood of the universe rule
+    fn cell_neighbours_count(&self, row: usize, column: usize) -> u16 {
+        let neighbourhood = self.rule.neighbourhood();
+        if self.rule.is_life_like() {
+            return self.cell_living_neighbours_count(row, column);
+        }
+        if neighbourhood.grid_offsets(row, column).is_some() {
+            return self.cell_grid_neighbours_count(row, column);
+        }
 
-            UniverseCell::Dead => {
-                if alive_cells_around == 3 {
-                    UniverseCell::Alive
-                } else {
-                    UniverseCell::Dead
+        let range = self.rule.range() as isize;
+        let mut count = 0;
+        for delta_row in -range..=range {
+            for delta_column in -range..=range {
+                let within_range = match neighbourhood {
+                    UniverseNeighbourhood::VonNeumann => {
+                        delta_row.abs() + delta_column.abs() <= range
+                    }
+                    _ => true,
+                };
+                let is_center = delta_row == 0 && delta_column == 0;
+                if within_range
+                    && (!is_center || self.rule.include_center())
+                    && self.is_alive_at(row as isize + delta_row, column as isize + delta_column)
+                {
+                    count += 1;
                 }
             }
         }
+        count
     }
 
-    /// Counts living adiacents cells for a given cell at `row`x`column` coordinates
-    fn cell_living_neighbours_count(&self, row: usize, column: usize) -> u8 {
-        let mut count = 0;
-        let rows = [self.rows - 1, 0, 1];
-        let cols = [self.columns - 1, 0, 1];
-        for delta_row in rows.iter() {
-            for delta_col in cols.iter() {
-                if *delta_row == 0 && *delta_col == 0 {
-                    continue;
+    /// Counts the living neighbours of every cell, according to the
+    /// neighbourhood of the universe rule, into `buffers.counts`.
+    /// Counts are indexed like cells
+    fn neighbour_counts(&self, buffers: &mut TickBuffers) {
+        let neighbourhood = self.rule.neighbourhood();
+        let counts = &mut buffers.counts;
+        counts.clear();
+        counts.resize(self.cells.len(), 0);
+
+        if self.rule.is_life_like() || neighbourhood.grid_offsets(0, 0).is_some() {
+            for_each_row_band(counts, self.columns, &self.workers, |rows, counts| {
+                for (offset, count) in counts.iter_mut().enumerate() {
+                    let row = rows.start + offset / self.columns;
+                    *count = self.cell_neighbours_count(row, offset % self.columns);
                 }
+            });
+            return;
+        }
 
-                let neighbor_row = (row + delta_row) % self.rows;
-                let neighbor_col = (column + delta_col) % self.columns;
-                let idx = self.get_index(neighbor_row, neighbor_col);
-                match self.cells[idx] {
-                    UniverseCell::Alive => count += 1,
-                    UniverseCell::Dead => (),
-                };
+        let range = self.rule.range() as usize;
+        let padded_columns = self.padded_alive_grid(range, &mut buffers.padded);
+        let (padded, sums) = (&buffers.padded, &mut buffers.sums);
+        match neighbourhood {
+            UniverseNeighbourhood::VonNeumann => {
+                self.von_neumann_range_counts(padded, padded_columns, range, sums, counts)
+            }
+            _ => self.moore_range_counts(padded, padded_columns, range, sums, counts),
+        }
+
+        // Areas above include the cell itself
+        if !self.rule.include_center() {
+            for (count, cell) in counts.iter_mut().zip(self.cells.iter()) {
+                if cell.is_alive() {
+                    *count -= 1;
+                }
             }
         }
-        count
+    }
+
+    /// Fills `padded` with a copy of the living cells (as 1s) surrounded by a border
+    /// of `pad` cells found past the edges of the universe, according to its topology.
+    /// Returns the number of columns of the padded grid
+    fn padded_alive_grid(&self, pad: usize, padded: &mut Vec<u32>) -> usize {
+        let (padded_rows, padded_columns) = (self.rows + 2 * pad, self.columns + 2 * pad);
+        padded.clear();
+        padded.reserve(padded_rows * padded_columns);
+        for padded_row in 0..padded_rows {
+            let row = padded_row as isize - pad as isize;
+            for padded_column in 0..padded_columns {
+                let column = padded_column as isize - pad as isize;
+                padded.push(self.is_alive_at(row, column) as u32);
+            }
+        }
+
+        padded_columns
+    }
+
+    /// Counts living cells in the (2 * `range` + 1) sided square around each cell
+    /// using a summed-area table, so that the cost does not depend on `range`
+    fn moore_range_counts(
+        &self,
+        padded: &[u32],
+        padded_columns: usize,
+        range: usize,
+        table: &mut Vec<u32>,
+        counts: &mut [u16],
+    ) {
+        let padded_rows = padded.len() / padded_columns;
+        let table_columns = padded_columns + 1;
+
+        // table[r][c] holds the sum of all the padded cells above and to the left of (r, c)
+        table.clear();
+        table.resize((padded_rows + 1) * table_columns, 0);
+        for row in 0..padded_rows {
+            let mut row_sum = 0;
+            for column in 0..padded_columns {
+                row_sum += padded[row * padded_columns + column];
+                table[(row + 1) * table_columns + column + 1] =
+                    table[row * table_columns + column + 1] + row_sum;
+            }
+        }
+
+        let side = 2 * range + 1;
+        for_each_row_band(counts, self.columns, &self.workers, |rows, counts| {
+            for (offset, count) in counts.iter_mut().enumerate() {
+                let (row, column) = (rows.start + offset / self.columns, offset % self.columns);
+                let (top, left) = (row * table_columns, column);
+                let (bottom, right) = ((row + side) * table_columns, column + side);
+                let sum = table[bottom + right] + table[top + left]
+                    - table[top + right]
+                    - table[bottom + left];
+                *count = sum as u16;
+            }
+        });
+    }
+
+    /// Counts living cells within a Manhattan distance of `range` from each cell,
+    /// summing one row segment of the diamond at a time from per-row prefix sums
+    fn von_neumann_range_counts(
+        &self,
+        padded: &[u32],
+        padded_columns: usize,
+        range: usize,
+        prefix: &mut Vec<u32>,
+        counts: &mut [u16],
+    ) {
+        let prefix_columns = padded_columns + 1;
+        prefix.clear();
+        prefix.resize(padded.len() / padded_columns * prefix_columns, 0);
+        for (row, cells) in padded.chunks(padded_columns).enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                prefix[row * prefix_columns + column + 1] =
+                    prefix[row * prefix_columns + column] + cell;
+            }
+        }
+
+        for_each_row_band(counts, self.columns, &self.workers, |rows, counts| {
+            for (offset, count) in counts.iter_mut().enumerate() {
+                let (row, column) = (rows.start + offset / self.columns, offset % self.columns);
+                let center_column = column + range;
+                let mut sum = 0;
+                for padded_row in row..=row + 2 * range {
+                    let half_width = range - padded_row.abs_diff(row + range);
+                    let line = padded_row * prefix_columns;
+                    sum += prefix[line + center_column + half_width + 1]
+                        - prefix[line + center_column - half_width];
+                }
+                *count = sum as u16;
+            }
+        });
     }
 
     /// Iterates over this universe and computes its next generation.
     /// Alters the struct in-place.
     pub fn tick(&mut self) {
-        let mut new_state = Self::new(self.columns, self.rows);
+        if self.topology.is_unbounded() && SparsePlane::supports(&self.rule) {
+            self.tick_unbounded();
+        } else if self.rule.is_life_like()
+            && self.rule.states() == 2
+            && BitGrid::supports(&self.topology)
+        {
+            self.tick_packed();
+        } else {
+            // Other rules only evolve within the viewport of unbounded universes
+            self.bits = None;
+            self.plane = None;
+            let mut buffers = std::mem::take(&mut self.buffers);
+            if self.find_active_cells(&mut buffers) {
+                self.tick_active(&mut buffers);
+            } else {
+                self.tick_all(&mut buffers);
+            }
+            self.buffers = buffers;
+        }
 
-        let mut delta: Vec<UniversePoint> = Vec::with_capacity(self.cells.capacity());
-        for point in self.iter_cells() {
-            let index = self.get_index(point.row(), point.column());
-            let cell_current_state = point.cell();
-            let cell_next_state = self.cell_next_state(point.row(), point.column());
-            let death_map_item_ref = new_state.death_map.get_mut(index).unwrap();
-            new_state.cells[index] = cell_next_state;
-
-            if cell_next_state != *cell_current_state {
-                match cell_next_state {
-                    UniverseCell::Alive => {
-                        // Cell becomes alive
-                        *death_map_item_ref = 0.0;
-                    }
-                    UniverseCell::Dead => {
-                        // Cell dies
-                        *death_map_item_ref = UNIVERSE_CELL_INITIAL_CORPSE_HEAT;
+        self.decay_corpses();
+        self.edited_cells.clear();
+    }
+
+    /// Finds the cells whose state may change in the next generation: those
+    /// changed by the last generation or set since then, and their neighbours.
+    /// Returns `false` when they are not known, or too many to be worth finding
+    fn find_active_cells(&self, buffers: &mut TickBuffers) -> bool {
+        let Some(last_delta) = self.last_delta.as_ref() else {
+            return false;
+        };
+        let limit = self.cells.len() / 4;
+        if last_delta.len() + self.edited_cells.len() > limit {
+            return false;
+        }
+
+        let (reach_rows, reach_columns) = self.rule.reach();
+        let (reach_rows, reach_columns) = (reach_rows as isize, reach_columns as isize);
+        let changed = last_delta
+            .iter()
+            .map(|point| (point.row(), point.column()))
+            .chain(self.edited_cells.iter().copied());
+
+        let (active, marked) = (&mut buffers.active, &mut buffers.marked);
+        active.clear();
+        marked.resize(self.cells.len(), false);
+        for (row, column) in changed {
+            for delta_row in -reach_rows..=reach_rows {
+                for delta_column in -reach_columns..=reach_columns {
+                    let around = self.topology.wrap(
+                        row as isize + delta_row,
+                        column as isize + delta_column,
+                        self.rows,
+                        self.columns,
+                    );
+                    if let Some((around_row, around_column)) = around {
+                        let index = self.get_index(around_row, around_column);
+                        if !marked[index] {
+                            marked[index] = true;
+                            active.push(index);
+                        }
                     }
                 }
+            }
+            if active.len() > limit {
+                break;
+            }
+        }
+
+        // Leaves marks clean for the next generation
+        for index in active.iter() {
+            marked[*index] = false;
+        }
+
+        active.len() <= limit
+    }
+
+    /// Computes the next state of the active cells only, every other
+    /// cell being known to keep its current state
+    fn tick_active(&mut self, buffers: &mut TickBuffers) {
+        buffers.changes.clear();
+        buffers
+            .changes
+            .extend(buffers.active.iter().filter_map(|index| {
+                let current = self.cells[*index];
+                let neighbours =
+                    self.cell_neighbours_count(index / self.columns, index % self.columns);
+                let next = self.rule.next_cell(current, neighbours);
+                (next != current).then_some((*index, next))
+            }));
+
+        let mut delta = self.take_delta_buffer();
+        for (index, cell) in buffers.changes.iter() {
+            self.cells[*index] = *cell;
+            delta.push(UniversePoint::new(
+                index / self.columns,
+                index % self.columns,
+                *cell,
+                0.0,
+            ));
+        }
+
+        self.generations += 1;
+        self.last_delta = Some(delta);
+    }
+
+    /// Computes the next state of every cell into the back buffers,
+    /// then swaps them with the current ones
+    fn tick_all(&mut self, buffers: &mut TickBuffers) {
+        self.neighbour_counts(buffers);
+        buffers.cells.resize(self.cells.len(), UniverseCell::Dead);
+
+        let mut delta = self.take_delta_buffer();
+        for (index, (current, next)) in self.cells.iter().zip(buffers.cells.iter_mut()).enumerate()
+        {
+            *next = self.rule.next_cell(*current, buffers.counts[index]);
+            if *next != *current {
                 delta.push(UniversePoint::new(
-                    point.row(),
-                    point.column(),
-                    cell_next_state,
-                    *death_map_item_ref,
+                    index / self.columns,
+                    index % self.columns,
+                    *next,
+                    0.0,
                 ));
+            }
+        }
+
+        std::mem::swap(&mut self.cells, &mut buffers.cells);
+        self.generations += 1;
+        self.last_delta = Some(delta);
+    }
+
+    /// Computes the next generation on the bit packed copy of the living cells,
+    /// then updates only the cells that changed
+    fn tick_packed(&mut self) {
+        let mut bits = match self.bits.take() {
+            Some(bits) => bits,
+            None => {
+                let mut bits = BitGrid::new(self.rows, self.columns);
+                for (index, cell) in self.cells.iter_mut().enumerate() {
+                    match cell {
+                        UniverseCell::Alive => {
+                            bits.set(index / self.columns, index % self.columns, true)
+                        }
+                        // Left over by a previous multi-state rule
+                        UniverseCell::Dying(_) => *cell = UniverseCell::Dead,
+                        UniverseCell::Dead => (),
+                    }
+                }
+                bits
+            }
+        };
+
+        let (birth, survival) = self.rule_masks();
+        bits.step(birth, survival, &self.topology, &self.workers);
+
+        let mut delta = self.take_delta_buffer();
+        delta.reserve(bits.changes_count());
+        bits.for_each_change(|row, column, alive| {
+            let cell = if alive {
+                UniverseCell::Alive
             } else {
-                // Dead cell corpse keeps freezing
-                if *cell_current_state == UniverseCell::Dead && *death_map_item_ref > 0.0 {
-                    *death_map_item_ref = point.corpse_heat() - UNIVERSE_DEFAULT_FREEZE_RATE;
+                UniverseCell::Dead
+            };
+            self.cells[row * self.columns + column] = cell;
+            delta.push(UniversePoint::new(row, column, cell, 0.0));
+        });
+
+        self.bits = Some(bits);
+        self.generations += 1;
+        self.last_delta = Some(delta);
+    }
+
+    /// Masks with the n-th bit set when n living neighbours make
+    /// a cell be born, and when they make a cell survive
+    fn rule_masks(&self) -> (u16, u16) {
+        let counts_mask = |accepts: &dyn Fn(u16) -> bool| {
+            (0..=8)
+                .filter(|count| accepts(*count))
+                .fold(0u16, |mask, count| mask | 1 << count)
+        };
+        (
+            counts_mask(&|count| self.rule.is_birth(count)),
+            counts_mask(&|count| self.rule.is_survival(count)),
+        )
+    }
+
+    /// Cools down the corpses of the previous generations,
+    /// then heats up the cells that died in the last one
+    fn decay_corpses(&mut self) {
+        let decay = self.corpse_decay;
+        let death_map = &mut self.death_map;
+        self.warm_cells.retain(|index| {
+            death_map[*index] = decay.cool_down(death_map[*index]);
+            death_map[*index] > 0.0
+        });
+
+        let Some(last_delta) = self.last_delta.as_mut() else {
+            return;
+        };
+        for point in last_delta.iter_mut() {
+            if *point.cell() == UniverseCell::Dead {
+                let index = point.row() * self.columns + point.column();
+                let heat = decay.heat_up(death_map[index]);
+                if death_map[index] == 0.0 && heat > 0.0 {
+                    self.warm_cells.push(index);
                 }
+                death_map[index] = heat;
+                point.set_corpse_heat(heat);
             }
         }
-        self.cells = new_state.cells.clone();
-        self.death_map = new_state.death_map.clone();
+    }
+
+    /// Makes every corpse cold at once
+    fn clear_corpses(&mut self) {
+        self.death_map.fill(0.0);
+        self.warm_cells.clear();
+    }
+
+    /// Empties the last delta, keeping its storage to hold the next one
+    fn take_delta_buffer(&mut self) -> Vec<UniversePoint> {
+        let mut delta = self.last_delta.take().unwrap_or_default();
+        delta.clear();
+        delta
+    }
+
+    /// Changes of the last generation tell nothing about the next one after
+    /// the rule or the topology change, so that every cell has to be computed
+    fn forget_changes(&mut self) {
+        self.last_delta = None;
+        self.bits = None;
+    }
+
+    /// Gets the plane of an unbounded universe, placing the
+    /// living cells of the viewport on a new one if needed
+    fn plane_mut(&mut self) -> &mut SparsePlane {
+        if self.plane.is_none() {
+            let mut plane = SparsePlane::new();
+            let (top, left) = self.viewport_origin;
+            for (index, cell) in self.cells.iter_mut().enumerate() {
+                match cell {
+                    UniverseCell::Alive => plane.set(
+                        top + (index / self.columns) as i64,
+                        left + (index % self.columns) as i64,
+                        true,
+                    ),
+                    // Left over by a previous multi-state rule
+                    UniverseCell::Dying(_) => *cell = UniverseCell::Dead,
+                    UniverseCell::Dead => (),
+                }
+            }
+            self.plane = Some(plane);
+        }
+
+        self.plane.as_mut().unwrap()
+    }
+
+    /// Computes the next generation on the plane of an unbounded universe,
+    /// then updates the cells of the viewport that changed
+    fn tick_unbounded(&mut self) {
+        let (birth, survival) = self.rule_masks();
+        self.plane_mut();
+        let plane = self.plane.as_mut().unwrap();
+        plane.step(birth, survival, &self.workers);
+
+        let mut delta = self.take_delta_buffer();
+        let (top, left) = self.viewport_origin;
+        let plane = self.plane.as_ref().unwrap();
+
+        // Cells of the viewport dying first, then the ones being born
+        for (index, cell) in self.cells.iter_mut().enumerate() {
+            let (row, column) = (index / self.columns, index % self.columns);
+            if cell.is_alive() && !plane.get(top + row as i64, left + column as i64) {
+                *cell = UniverseCell::Dead;
+                delta.push(UniversePoint::new(row, column, *cell, 0.0));
+            }
+        }
+        plane.for_each_alive_in(
+            top,
+            left,
+            self.rows as i64,
+            self.columns as i64,
+            |row, column| {
+                let (row, column) = ((row - top) as usize, (column - left) as usize);
+                let index = row * self.columns + column;
+                if !self.cells[index].is_alive() {
+                    self.cells[index] = UniverseCell::Alive;
+                    delta.push(UniversePoint::new(row, column, UniverseCell::Alive, 0.0));
+                }
+            },
+        );
+
         self.generations += 1;
         self.last_delta = Some(delta);
     }
 
+    /// Fills the viewport of an unbounded universe with the cells of its plane
+    fn refresh_viewport(&mut self) {
+        self.cells.fill(UniverseCell::Dead);
+        self.clear_corpses();
+        self.bits = None;
+        self.last_delta = None;
+
+        let (top, left) = self.viewport_origin;
+        if let Some(plane) = self.plane.as_ref() {
+            plane.for_each_alive_in(
+                top,
+                left,
+                self.rows as i64,
+                self.columns as i64,
+                |row, column| {
+                    let index = (row - top) as usize * self.columns + (column - left) as usize;
+                    self.cells[index] = UniverseCell::Alive;
+                },
+            );
+        }
+    }
+
+    /// Moves the viewport of an unbounded universe by `rows` and `columns` cells.
+    /// Does nothing on bounded universes
+    pub fn pan(&mut self, rows: i64, columns: i64) {
+        if !self.topology.is_unbounded() || (rows == 0 && columns == 0) {
+            return;
+        }
+
+        self.plane_mut();
+        self.viewport_origin = (
+            self.viewport_origin.0 + rows,
+            self.viewport_origin.1 + columns,
+        );
+        self.refresh_viewport();
+    }
+
+    /// Gets the plane coordinates of the top left cell of the viewport
+    /// of an unbounded universe
+    pub fn viewport_origin(&self) -> (i64, i64) {
+        self.viewport_origin
+    }
+
+    /// Resizes the viewport of an unbounded universe to `rows`x`columns`
+    /// cells, and fills it with the cells of its plane
+    fn resize_viewport(&mut self, rows: usize, columns: usize) {
+        self.plane_mut();
+        self.rows = rows;
+        self.columns = columns;
+        self.cells = vec![UniverseCell::Dead; rows * columns];
+        self.death_map = vec![0.0; rows * columns];
+        self.buffers = TickBuffers::default();
+        self.edited_cells.clear();
+        self.soup = None;
+        self.refresh_viewport();
+    }
+
+    /// Rebuilds the cells and the death map on a `rows`x`columns` grid, the
+    /// cell at `row`x`column` moving to where `place` puts it, or being
+    /// dropped when `place` puts it nowhere. Cells nothing is moved to are dead
+    fn rearrange<F: Fn(usize, usize) -> Option<(usize, usize)>>(
+        &mut self,
+        rows: usize,
+        columns: usize,
+        place: F,
+    ) {
+        let mut cells = vec![UniverseCell::Dead; rows * columns];
+        let mut death_map = vec![0.0; rows * columns];
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                if let Some((new_row, new_column)) = place(row, column) {
+                    let (index, new_index) =
+                        (self.get_index(row, column), new_row * columns + new_column);
+                    cells[new_index] = self.cells[index];
+                    death_map[new_index] = self.death_map[index];
+                }
+            }
+        }
+
+        self.rows = rows;
+        self.columns = columns;
+        self.cells = cells;
+        self.death_map = death_map;
+        self.warm_cells = (0..self.death_map.len())
+            .filter(|index| self.death_map[*index] > 0.0)
+            .collect();
+        self.bits = None;
+        self.plane = None;
+        self.buffers = TickBuffers::default();
+        self.last_delta = None;
+        self.edited_cells.clear();
+        self.fit_rule_range();
+
+        // The soup this universe was filled with does not give these cells anymore
+        self.soup = None;
+    }
+
+    /// Resizes this universe to `rows`x`columns` cells, keeping `anchor` in place.
+    /// Cells past the new edges are dropped, and the new ones are dead.
+    /// Unbounded universes keep all of their cells, only their viewport is resized
+    pub fn resize(&mut self, rows: usize, columns: usize, anchor: ResizeAnchor) {
+        let (rows, columns) = (rows.max(1), columns.max(1));
+        let (offset_rows, offset_columns) = anchor.offset(self.rows, self.columns, rows, columns);
+        if self.topology.is_unbounded() {
+            self.plane_mut();
+            self.viewport_origin = (
+                self.viewport_origin.0 - offset_rows as i64,
+                self.viewport_origin.1 - offset_columns as i64,
+            );
+            self.resize_viewport(rows, columns);
+            return;
+        }
+
+        self.rearrange(rows, columns, |row, column| {
+            let (row, column) = (row as isize + offset_rows, column as isize + offset_columns);
+            ((0..rows as isize).contains(&row) && (0..columns as isize).contains(&column))
+                .then_some((row as usize, column as usize))
+        });
+    }
+
+    /// Rotates or mirrors all of the cells of this universe. Cylinders turned
+    /// by a quarter have their joined edges swapped, so that patterns evolve
+    /// the same as before. Unbounded universes have their whole plane
+    /// transformed around the viewport.
+    /// Returns `false`, leaving the universe untouched, when cells would not
+    /// keep their neighbours: Klein bottles turned by a quarter, and
+    /// hexagonal or triangular grids transformed in ways their cells can not be
+    pub fn transform(&mut self, transform: UniverseTransform) -> bool {
+        if !transform.preserves(self.rule.neighbourhood(), self.rows, self.columns) {
+            return false;
+        }
+        if transform.swaps_axes() {
+            let Some(topology) = self.topology.transposed() else {
+                return false;
+            };
+            self.topology = topology;
+        }
+
+        let (rows, columns) = transform.dimensions(self.rows, self.columns);
+        let (old_rows, old_columns) = (self.rows as i64, self.columns as i64);
+
+        if self.topology.is_unbounded() {
+            let (top, left) = self.viewport_origin;
+            let plane = std::mem::take(self.plane_mut());
+            let mut transformed = SparsePlane::new();
+            plane.for_each_alive(|row, column| {
+                let (row, column) =
+                    transform.place(row - top, column - left, old_rows, old_columns);
+                transformed.set(top + row, left + column, true);
+            });
+            self.plane = Some(transformed);
+            self.resize_viewport(rows, columns);
+            return true;
+        }
+
+        self.rearrange(rows, columns, |row, column| {
+            let (row, column) = transform.place(row as i64, column as i64, old_rows, old_columns);
+            Some((row as usize, column as usize))
+        });
+        true
+    }
+
+    /// Moves all of the cells of this universe by `rows` rows down and `columns`
+    /// columns right, cells pushed past an edge coming back from the opposite one
+    /// whatever the topology. Unbounded universes have their whole plane moved
+    pub fn shift(&mut self, rows: i64, columns: i64) {
+        if self.topology.is_unbounded() {
+            let plane = std::mem::take(self.plane_mut());
+            let mut shifted = SparsePlane::new();
+            plane.for_each_alive(|row, column| shifted.set(row + rows, column + columns, true));
+            self.plane = Some(shifted);
+            self.soup = None;
+            self.refresh_viewport();
+            return;
+        }
+
+        let (universe_rows, universe_columns) = (self.rows, self.columns);
+        self.rearrange(universe_rows, universe_columns, |row, column| {
+            Some((
+                (row as i64 + rows).rem_euclid(universe_rows as i64) as usize,
+                (column as i64 + columns).rem_euclid(universe_columns as i64) as usize,
+            ))
+        });
+    }
+
+    /// Crops this universe to the smallest rectangle holding all of the cells
+    /// that are not dead, with `margin` dead cells around it. Unbounded universes
+    /// have their viewport fitted onto all of the living cells of their plane.
+    /// Universes are at most `UNIVERSE_MAX_SIDE` cells wide and tall, and are
+    /// centered on the cells when these spread further.
+    /// Returns `false`, leaving the universe untouched, when every cell is dead
+    pub fn crop_to_content(&mut self, margin: usize) -> bool {
+        // The number of cells of a side holding `cells` cells with their margins,
+        // and how many cells come before the first of them
+        let fit = |cells: usize| {
+            let side = (cells + 2 * margin).min(UNIVERSE_MAX_SIDE);
+            (
+                side,
+                margin as i64 - ((cells + 2 * margin - side) / 2) as i64,
+            )
+        };
+
+        if self.topology.is_unbounded() {
+            let mut bounds = None;
+            self.plane_mut()
+                .for_each_alive(|row, column| extend_bounds(&mut bounds, row, column));
+            let Some((top, left, bottom, right)) = bounds else {
+                return false;
+            };
+
+            let (rows, before_rows) = fit((bottom - top + 1) as usize);
+            let (columns, before_columns) = fit((right - left + 1) as usize);
+            self.viewport_origin = (top - before_rows, left - before_columns);
+            self.resize_viewport(rows, columns);
+            return true;
+        }
+
+        let mut bounds = None;
+        for (index, cell) in self.cells.iter().enumerate() {
+            if *cell != UniverseCell::Dead {
+                extend_bounds(&mut bounds, index / self.columns, index % self.columns);
+            }
+        }
+        let Some((top, left, bottom, right)) = bounds else {
+            return false;
+        };
+
+        let (rows, before_rows) = fit(bottom - top + 1);
+        let (columns, before_columns) = fit(right - left + 1);
+        self.rearrange(rows, columns, |row, column| {
+            if !(top..=bottom).contains(&row) || !(left..=right).contains(&column) {
+                return None;
+            }
+            let row = usize::try_from((row as i64 - top as i64) + before_rows).ok()?;
+            let column = usize::try_from((column as i64 - left as i64) + before_columns).ok()?;
+            (row < rows && column < columns).then_some((row, column))
+        });
+        true
+    }
+
+    /// Gets a hash of the states of the cells, the same for universes
+    /// holding the same cells. Unbounded universes hash their whole plane,
+    /// the living cells of their viewport until they are placed on one
+    pub fn state_hash(&self) -> u64 {
+        if let Some(plane) = self.plane.as_ref() {
+            return plane.state_hash();
+        }
+
+        if self.topology.is_unbounded() && SparsePlane::supports(&self.rule) {
+            let mut plane = SparsePlane::new();
+            let (top, left) = self.viewport_origin;
+            for (index, cell) in self.cells.iter().enumerate() {
+                if cell.is_alive() {
+                    plane.set(
+                        top + (index / self.columns) as i64,
+                        left + (index % self.columns) as i64,
+                        true,
+                    );
+                }
+            }
+            return plane.state_hash();
+        }
+
+        let mut hasher = DefaultHasher::new();
+        self.cells.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Gets the points to set on `next`, the generation computed from this
+    /// universe, to bring it back to this one: the cells `next` changed and the
+    /// corpses this universe had, as they were here. `None` when `next` is not
+    /// the following generation, or when going back needs more than its cells,
+    /// as with unbounded universes, which also evolve past their viewport
+    pub fn reverse_delta(&self, next: &Universe) -> Option<Vec<UniversePoint>> {
+        if next.generations != self.generations + 1
+            || (next.rows, next.columns) != (self.rows, self.columns)
+            || self.plane.is_some()
+            || next.plane.is_some()
+        {
+            return None;
+        }
+
+        let changed = next
+            .last_delta
+            .as_ref()?
+            .iter()
+            .map(|point| self.get_index(point.row(), point.column()));
+        Some(
+            changed
+                .chain(self.warm_cells.iter().copied())
+                .map(|index| {
+                    UniversePoint::new(
+                        index / self.columns,
+                        index % self.columns,
+                        self.cells[index],
+                        self.death_map[index],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Brings this universe back by one generation, setting the points
+    /// `reverse_delta` gave when this generation was computed
+    pub fn step_back(&mut self, reverse_delta: &[UniversePoint]) {
+        self.last_delta = None;
+        self.edited_cells.clear();
+        for point in reverse_delta {
+            self.set_cell(point.row(), point.column(), *point.cell());
+            let index = self.get_index(point.row(), point.column());
+            self.death_map[index] = point.corpse_heat();
+        }
+
+        self.warm_cells = (0..self.death_map.len())
+            .filter(|index| self.death_map[*index] > 0.0)
+            .collect();
+        self.generations = self.generations.saturating_sub(1);
+    }
+
+    /// Computes the next `generations` generations at once, giving up as soon as
+    /// `cancelled` is set. Universes evolving with two states Moore rules are
+    /// computed on a HashLife engine, in a handful of steps, when they are unbounded
+    /// or when their living cells are too far from the edges to reach them in
+    /// that many generations. Any other universe is computed one generation at
+    /// a time, as its edges keep the cells close to them from evolving as they
+    /// would on a plane. Returns `false` when it gave up, the universe being left
+    /// at whatever generation it got to
+    pub fn skip_forward(&mut self, generations: u64, cancelled: &AtomicBool) -> bool {
+        if SparsePlane::supports(&self.rule)
+            && (self.topology.is_unbounded() || self.stays_clear_of_edges(generations))
+        {
+            return self.skip_forward_hashlife(generations, cancelled);
+        }
+
+        for _ in 0..generations {
+            if cancelled.load(Ordering::Relaxed) {
+                return false;
+            }
+            self.tick();
+        }
+        true
+    }
+
+    /// Whether the living cells of a bounded universe, growing by a cell
+    /// each generation at most, stay off its edges for `generations` generations
+    fn stays_clear_of_edges(&self, generations: u64) -> bool {
+        let Some((top, left, bottom, right)) = self.bounding_box() else {
+            return true;
+        };
+        let (last_row, last_column) = (self.rows as i64 - 1, self.columns as i64 - 1);
+        let clearance = top
+            .min(left)
+            .min(last_row - bottom)
+            .min(last_column - right);
+        clearance as u64 > generations
+    }
+
+    /// Computes the next `generations` generations of the living cells on a
+    /// HashLife engine, the whole plane of unbounded universes included,
+    /// giving up between two of its steps as soon as `cancelled` is set
+    fn skip_forward_hashlife(&mut self, generations: u64, cancelled: &AtomicBool) -> bool {
+        let mut engine = match HashLife::new(&self.rule) {
+            Ok(engine) => engine,
+            Err(_) => return false,
+        };
+        if self.topology.is_unbounded() {
+            self.plane_mut();
+        }
+        self.for_each_alive(|row, column| engine.set(row, column, true));
+
+        let finished = engine.advance(generations, cancelled);
+
+        if self.topology.is_unbounded() {
+            let mut plane = SparsePlane::new();
+            engine.for_each_alive(|row, column| plane.set(row, column, true));
+            self.plane = Some(plane);
+            self.refresh_viewport();
+        } else {
+            self.cells.fill(UniverseCell::Dead);
+            engine.for_each_alive_in(
+                0,
+                0,
+                self.rows as i64,
+                self.columns as i64,
+                |row, column| {
+                    self.cells[row as usize * self.columns + column as usize] = UniverseCell::Alive;
+                },
+            );
+            self.clear_corpses();
+            self.forget_changes();
+        }
+        self.edited_cells.clear();
+        self.generations += engine.generations();
+        finished
+    }
+
     /// Counts and returns the number of alive cells
-    /// in this universe
+    /// in this universe, on the whole plane of unbounded universes
     pub fn alive_cells_count(&self) -> usize {
-        self.cells
-            .iter()
-            .filter(|cell| match cell {
-                UniverseCell::Alive => true,
-                UniverseCell::Dead => false,
+        if let Some(plane) = self.plane.as_ref() {
+            return plane.population();
+        }
+
+        self.cells.iter().filter(|cell| cell.is_alive()).count()
+    }
+
+    /// Calls `f` with the coordinates of every living cell, in no particular
+    /// order. Unbounded universes give the coordinates on their whole plane
+    fn for_each_alive<F: FnMut(i64, i64)>(&self, mut f: F) {
+        if let Some(plane) = self.plane.as_ref() {
+            plane.for_each_alive(f);
+            return;
+        }
+
+        let (top, left) = if self.topology.is_unbounded() {
+            self.viewport_origin
+        } else {
+            (0, 0)
+        };
+        for (index, cell) in self.cells.iter().enumerate() {
+            if cell.is_alive() {
+                f(
+                    top + (index / self.columns) as i64,
+                    left + (index % self.columns) as i64,
+                );
+            }
+        }
+    }
+
+    /// Gets the coordinates of every living cell, in no particular order.
+    /// Unbounded universes give the coordinates on their whole plane
+    pub fn alive_cells(&self) -> Vec<(i64, i64)> {
+        let mut cells = vec![];
+        self.for_each_alive(|row, column| cells.push((row, column)));
+        cells
+    }
+
+    /// Gets the (top, left, bottom, right) rectangle holding every living cell.
+    /// Unbounded universes give the coordinates on their whole plane
+    pub fn bounding_box(&self) -> Option<(i64, i64, i64, i64)> {
+        if let Some(plane) = self.plane.as_ref() {
+            return plane.bounds();
+        }
+
+        let mut bounds = None;
+        self.for_each_alive(|row, column| extend_bounds(&mut bounds, row, column));
+        bounds
+    }
+
+    /// Counts the cells born and the cells that died going from `previous` to this
+    /// universe, as (births, deaths). `None` when this universe is not the generation
+    /// computed from `previous`, or does not have the same size
+    pub fn changes_from(&self, previous: &Universe) -> Option<(usize, usize)> {
+        if self.generations != previous.generations + 1
+            || (self.rows, self.columns) != (previous.rows, previous.columns)
+        {
+            return None;
+        }
+
+        match (self.plane.as_ref(), previous.plane.as_ref()) {
+            (Some(plane), Some(previous_plane)) => Some(plane.changes_from(previous_plane)),
+            (None, None) => {
+                let (mut births, mut deaths) = (0, 0);
+                for (cell, previous_cell) in self.cells.iter().zip(previous.cells.iter()) {
+                    match (previous_cell.is_alive(), cell.is_alive()) {
+                        (false, true) => births += 1,
+                        (true, false) => deaths += 1,
+                        _ => (),
+                    }
+                }
+                Some((births, deaths))
+            }
+            // The first generation of an unbounded universe placed on its plane
+            _ => {
+                let before: HashSet<(i64, i64)> = previous.alive_cells().into_iter().collect();
+                let after: HashSet<(i64, i64)> = self.alive_cells().into_iter().collect();
+                Some((
+                    after.difference(&before).count(),
+                    before.difference(&after).count(),
+                ))
+            }
+        }
+    }
+
+    /// Finds out how `object`, or the whole universe when `None`,
+    /// repeats: its period, and how far it moves over a period
+    pub fn motion(&self, object: Option<&[(i64, i64)]>) -> Result<Motion, MotionError> {
+        match object {
+            Some(object) => Motion::of_object(object, &self.rule),
+            None => Motion::of_universe(self),
+        }
+    }
+
+    /// Gets the living cells touching each other the cell at `row`x`column`
+    /// belongs to, in the coordinates `motion` takes, on the whole plane of
+    /// unbounded universes. `None` when that cell is not alive
+    pub fn object_at(&self, row: usize, column: usize) -> Option<ObjectCells> {
+        if row >= self.rows || column >= self.columns || !self.get_cell(row, column).0.is_alive() {
+            return None;
+        }
+
+        let (top, left) = if self.topology.is_unbounded() {
+            self.viewport_origin
+        } else {
+            (0, 0)
+        };
+        let cell = (top + row as i64, left + column as i64);
+        split_objects(&self.alive_cells())
+            .into_iter()
+            .find(|object| object.contains(&cell))
+    }
+
+    /// Gets roughly how many bytes the cells of this universe take up,
+    /// along with their packed copy or the plane they are seen from
+    pub fn heap_size(&self) -> usize {
+        self.cells.len() * std::mem::size_of::<UniverseCell>()
+            + self.death_map.len() * std::mem::size_of::<f64>()
+            + self.warm_cells.len() * std::mem::size_of::<usize>()
+            + self.last_delta.as_ref().map_or(0, |delta| {
+                delta.len() * std::mem::size_of::<UniversePoint>()
             })
-            .count()
+            + self.bits.as_ref().map_or(0, BitGrid::heap_size)
+            + self.plane.as_ref().map_or(0, SparsePlane::heap_size)
     }
 
     /// Counts and returns the number of dead cells
@@ -234,10 +1276,7 @@ impl Universe {
     pub fn dead_cells_count(&self) -> usize {
         self.cells
             .iter()
-            .filter(|cell| match cell {
-                UniverseCell::Alive => false,
-                UniverseCell::Dead => true,
-            })
+            .filter(|cell| **cell == UniverseCell::Dead)
             .count()
     }
 
@@ -259,12 +1298,76 @@ impl Universe {
         UniverseSnapshot::from(self)
     }
 
-    pub fn corpse_freeze_rate(&self) -> &f64 {
-        &self.corpse_freeze_rate
+    /// Gets the number of rows of this universe, or of its viewport
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Gets the number of columns of this universe, or of its viewport
+    pub fn columns(&self) -> usize {
+        self.columns
     }
 
-    pub fn set_corpse_freeze_rate(&mut self, value: f64) {
-        self.corpse_freeze_rate = value;
+    /// Gets the rule this universe evolves with
+    pub fn rule(&self) -> &UniverseRule {
+        &self.rule
+    }
+
+    /// Sets the rule this universe evolves with. Takes effect from the next generation.
+    /// Its range is narrowed down to what fits the universe, as `fit_rule_range` does
+    pub fn set_rule(&mut self, value: UniverseRule) {
+        self.rule = value;
+        self.fit_rule_range();
+        self.forget_changes();
+    }
+
+    /// Narrows the neighbourhood range of the rule down to the widest one fitting
+    /// the topology and the size of this universe, so that neighbourhoods wrapping
+    /// around joined edges do not count the same cells more than once
+    fn fit_rule_range(&mut self) {
+        let max_range = self.topology.max_range(self.rows, self.columns);
+        if self.rule.range() > max_range {
+            self.rule = self.rule.with_max_range(max_range);
+        }
+    }
+
+    /// Gets the topology of this universe
+    pub fn topology(&self) -> &UniverseTopology {
+        &self.topology
+    }
+
+    /// Sets the topology of this universe. Takes effect from the next generation
+    pub fn set_topology(&mut self, value: UniverseTopology) {
+        self.topology = value;
+        self.fit_rule_range();
+        self.forget_changes();
+        if !value.is_unbounded() {
+            self.plane = None;
+        }
+    }
+
+    /// Gets the number of threads computing each generation. Zero means one per core
+    pub fn worker_threads(&self) -> usize {
+        self.workers.threads()
+    }
+
+    /// Sets the number of threads computing each generation. Zero means one per core.
+    /// The threads are started the first time a generation needs them
+    pub fn set_worker_threads(&mut self, value: usize) {
+        if value != self.workers.threads() {
+            self.workers = WorkerPool::new(value);
+        }
+    }
+
+    /// Gets how dead cells fade out
+    pub fn corpse_decay(&self) -> &CorpseDecay {
+        &self.corpse_decay
+    }
+
+    /// Sets how dead cells fade out. Corpses keep the heat they have,
+    /// and cool down following `value` from the next generation on
+    pub fn set_corpse_decay(&mut self, value: CorpseDecay) {
+        self.corpse_decay = value;
     }
 }
 
@@ -297,6 +1400,90 @@ impl UniversePointMatrix for Universe {
     }
 }
 
+impl CellularAutomaton for Universe {
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn columns(&self) -> usize {
+        self.columns
+    }
+
+    fn states(&self) -> u8 {
+        self.rule.states()
+    }
+
+    fn state(&self, row: usize, column: usize) -> u8 {
+        self.cells[self.get_index(row, column)].state()
+    }
+
+    fn set_state(&mut self, row: usize, column: usize, state: u8) {
+        let state = state.min(self.rule.states() - 1);
+        self.set_cell(row, column, UniverseCell::from_state(state));
+    }
+
+    fn step(&mut self) {
+        self.tick();
+    }
+
+    fn generation(&self) -> u64 {
+        self.generations
+    }
+
+    /// Living cells take the cell color, and dying cells of multi-state
+    /// rules fade towards the background as they get older
+    fn state_color(&self, state: u8) -> StateColor {
+        match state {
+            0 => StateColor::Background,
+            1 => StateColor::Foreground,
+            state => StateColor::Faded((state - 1) as f32 / (self.rule.states() - 1) as f32),
+        }
+    }
+
+    /// Only living cells make up the population, not the dying ones.
+    /// Unbounded universes count the cells past their viewport as well
+    fn population(&self) -> usize {
+        self.alive_cells_count()
+    }
+
+    fn neighbourhood(&self) -> UniverseNeighbourhood {
+        self.rule.neighbourhood()
+    }
+
+    fn corpse_heat(&self, row: usize, column: usize) -> f64 {
+        self.corpse_decay
+            .visibility(self.death_map[self.get_index(row, column)])
+    }
+
+    fn skip_forward(&mut self, generations: u64, cancelled: &AtomicBool) -> bool {
+        Universe::skip_forward(self, generations, cancelled)
+    }
+
+    fn bounding_box(&self) -> Option<(i64, i64, i64, i64)> {
+        Universe::bounding_box(self)
+    }
+
+    fn snapshot(&self) -> Option<UniverseSnapshot> {
+        Some(UniverseSnapshot::from(self))
+    }
+
+    fn heap_size(&self) -> usize {
+        Universe::heap_size(self)
+    }
+
+    fn as_universe(&self) -> Option<&Universe> {
+        Some(self)
+    }
+
+    fn as_universe_mut(&mut self) -> Option<&mut Universe> {
+        Some(self)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn CellularAutomaton> {
+        Box::new(self.clone())
+    }
+}
+
 pub struct UniverseIterator<'a> {
     universe: &'a Universe,
     row: usize,
@@ -354,10 +1541,10 @@ impl fmt::Display for Universe {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for line in self.cells.as_slice().chunks(self.columns()) {
             for &cell in line {
-                let symbol = if cell == UniverseCell::Dead {
-                    '◻'
-                } else {
-                    '◼'
+                let symbol = match cell {
+                    UniverseCell::Dead => '◻',
+                    UniverseCell::Alive => '◼',
+                    UniverseCell::Dying(_) => '▪',
                 };
                 write!(f, "{symbol}")?;
             }
@@ -382,26 +1569,178 @@ pub struct UniverseSnapshot {
 
     #[serde(skip, default)]
     death_map: Vec<f64>,
+
+    rule: UniverseRule,
+
+    topology: UniverseTopology,
+
+    /// Plane coordinates of the top left cell of the viewport of unbounded universes
+    viewport_origin: (i64, i64),
+
+    /// Plane coordinates of all the living cells of unbounded universes,
+    /// including those outside of the viewport
+    plane_cells: Vec<(i64, i64)>,
+
+    /// How the universe was randomly filled when created, if it was
+    soup: Option<SoupOptions>,
+
+    /// The kind of automaton the snapshot was taken of. Cells of automata
+    /// other than Life-like universes hold their numeric states
+    automaton: AutomatonKind,
+
+    /// How dead cells of the universe fade out, for
+    /// snapshots taken since it could be configured
+    corpse_decay: Option<CorpseDecay>,
+}
+
+/// Starts snapshots saved along with the version of their format. Snapshots
+/// saved before formats had versions start with their number of rows instead
+const SNAPSHOT_MAGIC: [u8; 4] = *b"GoLS";
+
+/// The version of the snapshot format, to be bumped whenever its fields change.
+/// Snapshots are read field after field, so that one saved with other fields
+/// can not be told apart from a damaged one without it
+const SNAPSHOT_VERSION: u16 = 1;
+
+/// The last snapshot format saved without a version,
+/// before dead cells decay could be configured
+#[derive(Deserialize)]
+struct UnversionedUniverseSnapshot {
+    rows: usize,
+    columns: usize,
+    cells: Vec<UniverseCell>,
+    rule: UniverseRule,
+    topology: UniverseTopology,
+    viewport_origin: (i64, i64),
+    plane_cells: Vec<(i64, i64)>,
+    soup: Option<SoupOptions>,
+    automaton: AutomatonKind,
+}
+
+impl From<UnversionedUniverseSnapshot> for UniverseSnapshot {
+    fn from(unversioned: UnversionedUniverseSnapshot) -> Self {
+        UniverseSnapshot {
+            death_map: vec![0.0; unversioned.cells.len()],
+            rows: unversioned.rows,
+            columns: unversioned.columns,
+            cells: unversioned.cells,
+            rule: unversioned.rule,
+            topology: unversioned.topology,
+            viewport_origin: unversioned.viewport_origin,
+            plane_cells: unversioned.plane_cells,
+            soup: unversioned.soup,
+            automaton: unversioned.automaton,
+            corpse_decay: None,
+        }
+    }
+}
+
+/// The snapshot format used before rules were stored along with cells.
+/// Most bundled templates still use this format.
+#[derive(Deserialize)]
+struct LegacyUniverseSnapshot {
+    rows: usize,
+    columns: usize,
+    cells: Vec<UniverseCell>,
+}
+
+impl From<LegacyUniverseSnapshot> for UniverseSnapshot {
+    fn from(legacy: LegacyUniverseSnapshot) -> Self {
+        UniverseSnapshot {
+            death_map: vec![0.0; legacy.cells.len()],
+            rows: legacy.rows,
+            columns: legacy.columns,
+            cells: legacy.cells,
+            rule: UniverseRule::default(),
+            topology: UniverseTopology::default(),
+            viewport_origin: (0, 0),
+            plane_cells: vec![],
+            soup: None,
+            automaton: AutomatonKind::Life,
+            corpse_decay: None,
+        }
+    }
 }
 
 impl From<&Universe> for UniverseSnapshot {
     fn from(value: &Universe) -> Self {
+        let mut plane_cells = vec![];
+        if let Some(plane) = value.plane.as_ref() {
+            plane.for_each_alive(|row, column| plane_cells.push((row, column)));
+        }
+
         UniverseSnapshot {
             cells: value.cells.clone(),
             death_map: value.death_map.clone(),
             rows: value.rows(),
             columns: value.columns(),
+            rule: value.rule,
+            topology: value.topology,
+            viewport_origin: value.viewport_origin,
+            plane_cells,
+            soup: value.soup,
+            automaton: AutomatonKind::Life,
+            corpse_decay: Some(value.corpse_decay),
         }
     }
 }
 
 impl UniverseSnapshot {
+    /// Builds a snapshot of an automaton other than a Life-like
+    /// universe, out of the numeric state of each cell, row by row
+    pub(super) fn of_automaton(
+        automaton: AutomatonKind,
+        rows: usize,
+        columns: usize,
+        states: Vec<u8>,
+    ) -> Self {
+        UniverseSnapshot {
+            death_map: vec![0.0; states.len()],
+            rows,
+            columns,
+            cells: states.into_iter().map(UniverseCell::from_state).collect(),
+            rule: UniverseRule::default(),
+            topology: UniverseTopology::DeadBoundary,
+            viewport_origin: (0, 0),
+            plane_cells: vec![],
+            soup: None,
+            automaton,
+            corpse_decay: None,
+        }
+    }
+
     fn get_index(&self, row: usize, column: usize) -> usize {
         (row * self.columns) + column
     }
 
+    /// Writes this snapshot in the current format, after its version
     pub fn serialize(&self) -> Result<Vec<u8>, bincode::Error> {
-        bincode::serialize(self)
+        let mut bytes = SNAPSHOT_MAGIC.to_vec();
+        bytes.extend(SNAPSHOT_VERSION.to_le_bytes());
+        bincode::serialize_into(&mut bytes, self)?;
+        Ok(bytes)
+    }
+
+    pub fn rule(&self) -> &UniverseRule {
+        &self.rule
+    }
+
+    pub fn topology(&self) -> &UniverseTopology {
+        &self.topology
+    }
+
+    pub fn soup(&self) -> Option<&SoupOptions> {
+        self.soup.as_ref()
+    }
+
+    /// Gets the kind of automaton this snapshot was taken of
+    pub fn automaton(&self) -> AutomatonKind {
+        self.automaton
+    }
+
+    /// Gets how dead cells of the universe fade out, if the snapshot tells
+    pub fn corpse_decay(&self) -> Option<&CorpseDecay> {
+        self.corpse_decay.as_ref()
     }
 }
 
@@ -418,9 +1757,9 @@ impl UniversePointMatrix for UniverseSnapshot {
 
     fn get(&self, row: usize, column: usize) -> Option<UniversePoint> {
         let idx = self.get_index(row, column);
-        self.cells
-            .get(idx)
-            .map(|cell| UniversePoint::new(row, column, *cell, self.death_map[idx]))
+        self.cells.get(idx).map(|cell| {
+            UniversePoint::new(row, column, *cell, *self.death_map.get(idx).unwrap_or(&0.0))
+        })
     }
 
     fn set(
@@ -436,34 +1775,258 @@ impl UniversePointMatrix for UniverseSnapshot {
 #[derive(Debug)]
 pub enum SnapshotError {
     Invalid,
+
+    /// Saved with a newer version of the format, by a newer version of the application
+    UnsupportedVersion,
 }
 
 impl TryFrom<&Vec<u8>> for UniverseSnapshot {
     type Error = SnapshotError;
     fn try_from(value: &Vec<u8>) -> Result<Self, Self::Error> {
-        match bincode::deserialize::<Self>(value.as_ref()) {
-            Ok(snapshot) => Ok(snapshot),
-            Err(error) => {
-                glib::g_critical!(G_LOG_DOMAIN, "{}", error);
-                Err(SnapshotError::Invalid)
+        let snapshot = match value.strip_prefix(&SNAPSHOT_MAGIC) {
+            Some(versioned) => {
+                let (version, body) = versioned
+                    .split_first_chunk::<2>()
+                    .ok_or(SnapshotError::Invalid)?;
+                match u16::from_le_bytes(*version) {
+                    SNAPSHOT_VERSION => bincode::deserialize::<Self>(body),
+                    version => {
+                        glib::g_critical!(
+                            G_LOG_DOMAIN,
+                            "Unsupported snapshot format version {}",
+                            version
+                        );
+                        return Err(SnapshotError::UnsupportedVersion);
+                    }
+                }
             }
-        }
+            // Tries the last unversioned format first, as older ones make up its first fields
+            None => bincode::deserialize::<UnversionedUniverseSnapshot>(value)
+                .map(Self::from)
+                .or_else(|_| bincode::deserialize::<LegacyUniverseSnapshot>(value).map(Self::from)),
+        };
+
+        snapshot.map_err(|error| {
+            glib::g_critical!(G_LOG_DOMAIN, "{}", error);
+            SnapshotError::Invalid
+        })
     }
 }
 
 impl From<UniverseSnapshot> for Universe {
     fn from(snapshot: UniverseSnapshot) -> Self {
-        let mut death_map = Vec::with_capacity(snapshot.rows * snapshot.columns);
-        death_map.fill(0.0);
+        let death_map = vec![0.0; snapshot.rows * snapshot.columns];
+        let plane =
+            (snapshot.topology.is_unbounded() && !snapshot.plane_cells.is_empty()).then(|| {
+                let mut plane = SparsePlane::new();
+                for (row, column) in snapshot.plane_cells.iter() {
+                    plane.set(*row, *column, true);
+                }
+                plane
+            });
 
-        Self {
+        let mut universe = Self {
             rows: snapshot.rows,
             columns: snapshot.columns,
-            corpse_freeze_rate: UNIVERSE_DEFAULT_FREEZE_RATE,
+            rule: snapshot.rule,
+            topology: snapshot.topology,
+            bits: None,
+            plane,
+            viewport_origin: snapshot.viewport_origin,
+            soup: snapshot.soup,
+            workers: WorkerPool::default(),
+            corpse_decay: snapshot.corpse_decay.unwrap_or_default(),
+            warm_cells: vec![],
             death_map,
             cells: snapshot.cells,
             generations: 0,
             last_delta: None,
+            edited_cells: vec![],
+            buffers: TickBuffers::default(),
+        };
+        universe.fit_rule_range();
+        universe
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parallel::MIN_ROWS_PER_BAND;
+    use super::*;
+
+    fn soup(rows: usize, columns: usize, rule: &str, topology: UniverseTopology) -> Universe {
+        let mut universe = Universe::new_soup(rows, columns, SoupOptions::new(7));
+        universe.set_rule(rule.parse().unwrap());
+        universe.set_topology(topology);
+        universe
+    }
+
+    fn sorted_alive_cells(universe: &Universe) -> Vec<(i64, i64)> {
+        let mut cells = universe.alive_cells();
+        cells.sort_unstable();
+        cells
+    }
+
+    #[test]
+    fn hashlife_skip_matches_ticks_on_unbounded_plane() {
+        for (rule, generations) in [
+            ("B3/S23", 1),
+            ("B3/S23", 37),
+            ("B3/S23", 300),
+            ("B36/S23", 128),
+        ] {
+            let mut skipped = soup(48, 48, rule, UniverseTopology::Unbounded);
+            let mut ticked = skipped.clone();
+
+            assert!(skipped.skip_forward(generations, &AtomicBool::new(false)));
+            for _ in 0..generations {
+                ticked.tick();
+            }
+
+            assert_eq!(skipped.generation(), ticked.generation());
+            assert_eq!(
+                sorted_alive_cells(&skipped),
+                sorted_alive_cells(&ticked),
+                "{rule}"
+            );
+            assert_eq!(skipped.cells, ticked.cells, "{rule}");
+        }
+    }
+
+    #[test]
+    fn hashlife_skip_matches_ticks_away_from_edges() {
+        let small = soup(16, 16, "B3/S23", UniverseTopology::Torus);
+        for topology in [UniverseTopology::Torus, UniverseTopology::KleinBottle] {
+            let mut skipped = Universe::new(96, 96);
+            skipped.set_topology(topology);
+            for row in 0..16 {
+                for column in 0..16 {
+                    skipped.set_cell(row + 40, column + 40, *small.get_cell(row, column).0);
+                }
+            }
+            let mut ticked = skipped.clone();
+
+            let generations = 30;
+            assert!(skipped.stays_clear_of_edges(generations));
+            assert!(skipped.skip_forward(generations, &AtomicBool::new(false)));
+            for _ in 0..generations {
+                ticked.tick();
+            }
+
+            assert_eq!(skipped.generation(), ticked.generation());
+            assert_eq!(skipped.cells, ticked.cells, "{topology:?}");
+        }
+    }
+
+    #[test]
+    fn cancelled_skip_stops() {
+        for topology in [UniverseTopology::Torus, UniverseTopology::Unbounded] {
+            let mut universe = soup(32, 32, "B3/S23", topology);
+            assert!(!universe.skip_forward(u64::MAX, &AtomicBool::new(true)));
+            assert_eq!(universe.generation(), 0);
+        }
+    }
+
+    #[test]
+    fn worker_threads_do_not_change_generations() {
+        let rows = 5 * MIN_ROWS_PER_BAND;
+        // Packed first, then computed cell by cell: the generations rule settles
+        // enough to only compute its active cells, the others compute them all
+        let mut active_ticks = 0;
+        for rule in ["B3/S23", "B2/S34H", "B3/S23/C4", "R2,C0,M0,S3..6,B4..5,NM"] {
+            let mut options = SoupOptions::new(11);
+            options.set_density(0.2);
+            let mut sequential = Universe::new_soup(rows, 96, options);
+            sequential.set_rule(rule.parse().unwrap());
+            sequential.set_topology(UniverseTopology::Torus);
+            sequential.set_worker_threads(1);
+            let mut parallel = sequential.clone();
+            parallel.set_worker_threads(4);
+
+            let packed = rule == "B3/S23";
+            for generation in 0..60 {
+                if !packed && sequential.find_active_cells(&mut TickBuffers::default()) {
+                    active_ticks += 1;
+                }
+                sequential.tick();
+                parallel.tick();
+                assert_eq!(sequential.cells, parallel.cells, "{rule} at {generation}");
+            }
+            assert_eq!(sequential.bits.is_some(), packed, "{rule}");
+        }
+        assert!(active_ticks > 0);
+    }
+
+    #[test]
+    fn range_counts_match_cell_by_cell_counts() {
+        let topologies = [
+            UniverseTopology::Torus,
+            UniverseTopology::DeadBoundary,
+            UniverseTopology::KleinBottle,
+            UniverseTopology::CrossSurface,
+        ];
+        for rule in [
+            "R2,C0,M0,S3..6,B4..5,NM",
+            "R3,C0,M1,S8..14,B9..12,NN",
+            "R5,C0,M1,S34..58,B34..45,NM",
+        ] {
+            for topology in topologies {
+                let universe = soup(23, 31, rule, topology);
+                assert_eq!(universe.rule().to_string(), rule);
+                let mut buffers = TickBuffers::default();
+                universe.neighbour_counts(&mut buffers);
+                for row in 0..23 {
+                    for column in 0..31 {
+                        assert_eq!(
+                            buffers.counts[row * 31 + column],
+                            universe.cell_neighbours_count(row, column),
+                            "{rule} on {topology} at {row}x{column}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn packed_tick_matches_tick_all() {
+        let topologies = UniverseTopology::ALL.into_iter().filter(BitGrid::supports);
+        for topology in topologies {
+            for rule in ["B3/S23", "B36/S23", "B2/S", "B1357/S1357"] {
+                // Widths around the 64 cells of a packed word
+                for (rows, columns) in [(40, 64), (33, 70), (17, 129)] {
+                    let mut packed = soup(rows, columns, rule, topology);
+                    let mut unpacked = packed.clone();
+                    let mut buffers = TickBuffers::default();
+                    for generation in 0..60 {
+                        packed.tick();
+                        unpacked.tick_all(&mut buffers);
+                        assert_eq!(
+                            packed.cells, unpacked.cells,
+                            "{rule} on {topology} {rows}x{columns}, generation {generation}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn packed_tick_matches_tick_all_on_largest_universe() {
+        for topology in [UniverseTopology::Torus, UniverseTopology::DeadBoundary] {
+            let side = UNIVERSE_MAX_SIDE;
+            let mut packed = soup(side, side, "B3/S23", topology);
+            let mut unpacked = packed.clone();
+            let mut buffers = TickBuffers::default();
+            for generation in 0..4 {
+                packed.tick();
+                unpacked.tick_all(&mut buffers);
+                assert!(
+                    packed.cells == unpacked.cells,
+                    "{topology}, generation {generation}"
+                );
+            }
+            assert!(packed.bits.is_some());
         }
     }
 }
@@ -0,0 +1,205 @@
+use super::{AutomatonKind, CellularAutomaton, StateColor, UniversePointMatrix, UniverseSnapshot};
+use crate::i18n::i18n;
+
+/// The state of a cell of a Wireworld circuit
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum WireworldCell {
+    /// Nothing there. Stays empty forever
+    #[default]
+    Empty,
+
+    /// The head of an electron. Becomes a tail
+    Head,
+
+    /// The tail of an electron. Becomes a conductor
+    Tail,
+
+    /// A piece of wire. Becomes an electron head when
+    /// one or two of its neighbours are electron heads
+    Conductor,
+}
+
+impl WireworldCell {
+    /// Gets the numeric state of this cell, in the order the variants are declared
+    pub fn state(&self) -> u8 {
+        match self {
+            WireworldCell::Empty => 0,
+            WireworldCell::Head => 1,
+            WireworldCell::Tail => 2,
+            WireworldCell::Conductor => 3,
+        }
+    }
+
+    /// Builds a cell from its numeric state. States past the last one are conductors
+    pub fn from_state(state: u8) -> Self {
+        match state {
+            0 => WireworldCell::Empty,
+            1 => WireworldCell::Head,
+            2 => WireworldCell::Tail,
+            _ => WireworldCell::Conductor,
+        }
+    }
+}
+
+/// A Wireworld circuit: electrons made of a head and a tail
+/// flowing along conductors, on a grid with dead edges
+#[derive(Debug, Clone)]
+pub struct Wireworld {
+    rows: usize,
+    columns: usize,
+    cells: Vec<WireworldCell>,
+
+    /// Back buffer the next generation is written to
+    next: Vec<WireworldCell>,
+
+    generations: u64,
+}
+
+impl Wireworld {
+    pub fn new(rows: usize, columns: usize) -> Self {
+        Self {
+            rows,
+            columns,
+            cells: vec![WireworldCell::Empty; rows * columns],
+            next: vec![],
+            generations: 0,
+        }
+    }
+
+    fn get_index(&self, row: usize, column: usize) -> usize {
+        (row * self.columns) + column
+    }
+
+    /// Gets the cell at `row`x`column`
+    pub fn get_cell(&self, row: usize, column: usize) -> WireworldCell {
+        self.cells[self.get_index(row, column)]
+    }
+
+    /// Sets the cell at `row`x`column`
+    pub fn set_cell(&mut self, row: usize, column: usize, cell: WireworldCell) {
+        let index = self.get_index(row, column);
+        self.cells[index] = cell;
+    }
+
+    /// Counts the electron heads around the cell at `row`x`column`
+    fn heads_around(&self, row: usize, column: usize) -> usize {
+        let (rows, columns) = (self.rows as isize, self.columns as isize);
+        let mut heads = 0;
+        for delta_row in -1..=1 {
+            for delta_column in -1..=1 {
+                let (around_row, around_column) =
+                    (row as isize + delta_row, column as isize + delta_column);
+                if (delta_row, delta_column) == (0, 0)
+                    || !(0..rows).contains(&around_row)
+                    || !(0..columns).contains(&around_column)
+                {
+                    continue;
+                }
+
+                if self.get_cell(around_row as usize, around_column as usize) == WireworldCell::Head
+                {
+                    heads += 1;
+                }
+            }
+        }
+        heads
+    }
+}
+
+impl CellularAutomaton for Wireworld {
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn columns(&self) -> usize {
+        self.columns
+    }
+
+    fn states(&self) -> u8 {
+        4
+    }
+
+    fn state(&self, row: usize, column: usize) -> u8 {
+        self.get_cell(row, column).state()
+    }
+
+    fn set_state(&mut self, row: usize, column: usize, state: u8) {
+        self.set_cell(row, column, WireworldCell::from_state(state));
+    }
+
+    fn step(&mut self) {
+        let mut next = std::mem::take(&mut self.next);
+        next.clear();
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                next.push(match self.get_cell(row, column) {
+                    WireworldCell::Empty => WireworldCell::Empty,
+                    WireworldCell::Head => WireworldCell::Tail,
+                    WireworldCell::Tail => WireworldCell::Conductor,
+                    WireworldCell::Conductor => match self.heads_around(row, column) {
+                        1 | 2 => WireworldCell::Head,
+                        _ => WireworldCell::Conductor,
+                    },
+                });
+            }
+        }
+
+        self.next = std::mem::replace(&mut self.cells, next);
+        self.generations += 1;
+    }
+
+    fn generation(&self) -> u64 {
+        self.generations
+    }
+
+    /// Electrons are blue and red, conductors are yellow,
+    /// whatever the colors picked by the user
+    fn state_color(&self, state: u8) -> StateColor {
+        match WireworldCell::from_state(state) {
+            WireworldCell::Empty => StateColor::Background,
+            WireworldCell::Head => StateColor::Fixed(0.21, 0.52, 0.89),
+            WireworldCell::Tail => StateColor::Fixed(0.88, 0.11, 0.14),
+            WireworldCell::Conductor => StateColor::Fixed(0.96, 0.76, 0.07),
+        }
+    }
+
+    fn state_name(&self, state: u8) -> Option<String> {
+        Some(match WireworldCell::from_state(state) {
+            WireworldCell::Empty => i18n("Empty"),
+            WireworldCell::Head => i18n("Electron head"),
+            WireworldCell::Tail => i18n("Electron tail"),
+            WireworldCell::Conductor => i18n("Conductor"),
+        })
+    }
+
+    fn snapshot(&self) -> Option<UniverseSnapshot> {
+        Some(UniverseSnapshot::of_automaton(
+            AutomatonKind::Wireworld,
+            self.rows,
+            self.columns,
+            self.cells.iter().map(|cell| cell.state()).collect(),
+        ))
+    }
+
+    fn heap_size(&self) -> usize {
+        (self.cells.len() + self.next.len()) * std::mem::size_of::<WireworldCell>()
+    }
+
+    fn clone_boxed(&self) -> Box<dyn CellularAutomaton> {
+        Box::new(self.clone())
+    }
+}
+
+impl From<UniverseSnapshot> for Wireworld {
+    fn from(snapshot: UniverseSnapshot) -> Self {
+        let mut wireworld = Self::new(snapshot.rows(), snapshot.columns());
+        for row in 0..snapshot.rows() {
+            for column in 0..snapshot.columns() {
+                if let Some(point) = snapshot.get(row, column) {
+                    wireworld.set_state(row, column, point.cell().state());
+                }
+            }
+        }
+        wireworld
+    }
+}
@@ -0,0 +1,132 @@
+use crate::models::{search_soups, Census, SoupSearchOptions, UniverseRule};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// The command line flag running a soup search rather than the application
+pub const SOUP_SEARCH_FLAG: &str = "--soup-search";
+
+/// The file the summary of a search is written to, in the output directory
+const REPORT_FILE_NAME: &str = "soup-search-report.txt";
+
+/// The file the seeds of the soups that produced rare objects are written to
+const RARE_SEEDS_FILE_NAME: &str = "soup-search-rare-seeds.csv";
+
+/// The options taking a value
+const OPTIONS: [&str; 7] = [
+    "--soups",
+    "--seed",
+    "--size",
+    "--density",
+    "--rule",
+    "--threads",
+    "--output",
+];
+
+const USAGE: &str = "Usage: game-of-life --soup-search [OPTION…]
+
+Runs random soups to stability, without opening any window, and tallies
+the objects they turn into. Soups evolve on an unbounded plane, and can
+be recreated in the application from their seed, size and density.
+
+Options:
+  --soups COUNT        Number of soups to run (default: 1000)
+  --seed SEED          Seed of the first soup, the next ones counting up from it (default: random)
+  --size SIDE          Side of the square soups, in cells (default: 16)
+  --density PERCENT    Share of living cells in the soups (default: 40)
+  --rule RULE          Rule the soups evolve with (default: B3/S23)
+  --threads COUNT      Number of soups run at once (default: one per core)
+  --output DIRECTORY   Where the report and the seeds of rare objects are written (default: .)
+  --help               Show this help";
+
+/// Runs the soup search described by `args`, the command line arguments
+/// following `SOUP_SEARCH_FLAG`. Returns the exit code of the process
+pub fn run_soup_search(args: &[String]) -> i32 {
+    let (options, output) = match parse_arguments(args) {
+        Ok(Some(parsed)) => parsed,
+        Ok(None) => {
+            println!("{USAGE}");
+            return 0;
+        }
+        Err(error) => {
+            eprintln!("{error}\n\n{USAGE}");
+            return 2;
+        }
+    };
+
+    let soups = options.soups();
+    let results = search_soups(options, |done| {
+        if done % 100 == 0 || done == soups {
+            eprint!("\rSoups run: {done} of {soups}");
+        }
+    });
+    eprintln!();
+
+    let report = results.report();
+    print!("{report}");
+    if let Err(error) = std::fs::create_dir_all(&output) {
+        eprintln!("Unable to create {}: {}", output.display(), error);
+        return 1;
+    }
+    for (file_name, contents) in [
+        (REPORT_FILE_NAME, report),
+        (RARE_SEEDS_FILE_NAME, results.rare_seeds()),
+    ] {
+        let path = output.join(file_name);
+        if let Err(error) = std::fs::write(&path, contents) {
+            eprintln!("Unable to write {}: {}", path.display(), error);
+            return 1;
+        }
+    }
+    0
+}
+
+/// Reads the options of a search and the output directory from `args`.
+/// `None` when help was asked for
+fn parse_arguments(args: &[String]) -> Result<Option<(SoupSearchOptions, PathBuf)>, String> {
+    let mut options = SoupSearchOptions::default();
+    options.set_first_seed(rand::random());
+    let mut output = PathBuf::from(".");
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--help" {
+            return Ok(None);
+        }
+        if !OPTIONS.contains(&arg.as_str()) {
+            return Err(format!("Unknown option {arg}"));
+        }
+
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {arg}"))?;
+        let invalid = || format!("Invalid value for {arg}: {value}");
+        match arg.as_str() {
+            "--soups" => options.set_soups(value.parse().map_err(|_| invalid())?),
+            "--seed" => options.set_first_seed(value.parse().map_err(|_| invalid())?),
+            "--size" => match value.parse() {
+                Ok(side) if side > 0 => options.set_side(side),
+                _ => return Err(invalid()),
+            },
+            "--density" => match value.parse::<f64>() {
+                Ok(density) if (0.0..=100.0).contains(&density) => {
+                    options.set_density(density / 100.0)
+                }
+                _ => return Err(invalid()),
+            },
+            "--rule" => {
+                let rule = UniverseRule::from_str(value).map_err(|_| invalid())?;
+                if !Census::supports(&rule) {
+                    return Err(format!(
+                        "Soups can only be searched with two states Life-like rules, \
+                         without births out of nothing: {value}"
+                    ));
+                }
+                options.set_rule(rule);
+            }
+            "--threads" => options.set_threads(value.parse().map_err(|_| invalid())?),
+            _ => output = PathBuf::from(value),
+        }
+    }
+
+    Ok(Some((options, output)))
+}
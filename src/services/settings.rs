@@ -1,4 +1,7 @@
 use crate::config::{APPLICATION_ID, G_LOG_DOMAIN};
+use crate::models::{
+    CorpseDecay, DecayCurve, SoupOptions, SoupSymmetry, UniverseRule, UniverseTopology,
+};
 use glib::prelude::*;
 use gtk::gdk;
 use gtk::gio::prelude::{SettingsExt, SettingsExtManual};
@@ -28,6 +31,18 @@ impl GameOfLifeSettings {
         self.inner.uint("evolution-speed")
     }
 
+    pub fn worker_threads(&self) -> u32 {
+        self.inner.uint("worker-threads")
+    }
+
+    pub fn history_depth(&self) -> u32 {
+        self.inner.uint("history-depth")
+    }
+
+    pub fn pause_on_stabilization(&self) -> bool {
+        self.inner.boolean("pause-on-stabilization")
+    }
+
     pub fn window_width(&self) -> i32 {
         self.inner.int("window-width")
     }
@@ -117,6 +132,75 @@ impl GameOfLifeSettings {
             .expect("Could not store default universe height");
     }
 
+    pub fn universe_rule(&self) -> UniverseRule {
+        self.inner
+            .string("universe-rule")
+            .parse()
+            .unwrap_or_default()
+    }
+
+    pub fn set_universe_rule(&self, value: &UniverseRule) {
+        self.inner
+            .set_string("universe-rule", value.to_string().as_str())
+            .expect("Could not store default universe rule");
+    }
+
+    pub fn universe_topology(&self) -> UniverseTopology {
+        self.inner
+            .string("universe-topology")
+            .parse()
+            .unwrap_or_default()
+    }
+
+    pub fn set_universe_topology(&self, value: UniverseTopology) {
+        self.inner
+            .set_string("universe-topology", value.to_string().as_str())
+            .expect("Could not store default universe topology");
+    }
+
+    pub fn soup_density(&self) -> f64 {
+        self.inner.double("soup-density")
+    }
+
+    pub fn set_soup_density(&self, value: f64) {
+        self.inner
+            .set_double("soup-density", value)
+            .expect("Could not store soup density");
+    }
+
+    pub fn soup_box(&self) -> u32 {
+        self.inner.uint("soup-box")
+    }
+
+    pub fn set_soup_box(&self, value: u32) {
+        self.inner
+            .set_uint("soup-box", value)
+            .expect("Could not store soup box");
+    }
+
+    pub fn soup_symmetry(&self) -> SoupSymmetry {
+        self.inner
+            .string("soup-symmetry")
+            .parse()
+            .unwrap_or_default()
+    }
+
+    pub fn set_soup_symmetry(&self, value: SoupSymmetry) {
+        self.inner
+            .set_string("soup-symmetry", value.to_string().as_str())
+            .expect("Could not store soup symmetry");
+    }
+
+    /// Soup options for new random universes, with the stored
+    /// density, box and symmetry and a seed drawn at random
+    pub fn soup_options(&self) -> SoupOptions {
+        let mut soup = SoupOptions::random();
+        soup.set_density(self.soup_density());
+        soup.set_central_box(Some(self.soup_box() as usize));
+        soup.set_symmetry(self.soup_symmetry());
+        soup
+    }
+
     pub fn draw_cells_outline(&self) -> bool {
         self.inner.boolean("draw-cells-outline")
     }
@@ -125,6 +209,36 @@ impl GameOfLifeSettings {
         self.inner.boolean("fade-out-cells")
     }
 
+    pub fn corpse_heat(&self) -> f64 {
+        self.inner.double("corpse-heat")
+    }
+
+    pub fn corpse_decay_rate(&self) -> f64 {
+        self.inner.double("corpse-decay-rate")
+    }
+
+    pub fn corpse_decay_curve(&self) -> DecayCurve {
+        self.inner
+            .string("corpse-decay-curve")
+            .parse()
+            .unwrap_or_default()
+    }
+
+    pub fn set_corpse_decay_curve(&self, value: DecayCurve) {
+        self.inner
+            .set_string("corpse-decay-curve", value.to_string().as_str())
+            .expect("Could not store corpse decay curve");
+    }
+
+    /// How dead cells of the universes shown fade out
+    pub fn corpse_decay(&self) -> CorpseDecay {
+        CorpseDecay::new(
+            self.corpse_heat(),
+            self.corpse_decay_rate(),
+            self.corpse_decay_curve(),
+        )
+    }
+
     #[allow(dead_code)]
     pub fn set_draw_cells_outline(&self, value: bool) {
         self.inner
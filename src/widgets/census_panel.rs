@@ -0,0 +1,154 @@
+use crate::i18n::{group_digits, i18n};
+use crate::models::{Census, Motion, MotionDirection, ObjectKind};
+use gtk::{gio, glib};
+use gtk::{prelude::*, subclass::prelude::*, CompositeTemplate};
+
+/// Describes the speed of a moving pattern in c notation, as in "c/4 diagonal"
+pub fn speed_label(motion: &Motion) -> String {
+    let Some(speed) = motion.speed() else {
+        return String::new();
+    };
+    match motion.direction() {
+        Some(MotionDirection::Orthogonal) => i18n("{speed} orthogonal"),
+        Some(MotionDirection::Diagonal) => i18n("{speed} diagonal"),
+        _ => i18n("{speed} oblique"),
+    }
+    .replace("{speed}", &speed)
+}
+
+/// Describes the kind of objects in the census table
+fn kind_label(kind: ObjectKind) -> String {
+    match kind {
+        ObjectKind::StillLife => i18n("Still life"),
+        ObjectKind::Oscillator { period } => {
+            i18n("Oscillator, period {period}").replace("{period}", &period.to_string())
+        }
+        ObjectKind::Spaceship {
+            period,
+            displacement,
+        } => i18n("Spaceship, {speed}")
+            .replace("{speed}", &speed_label(&Motion::new(period, displacement))),
+        ObjectKind::Unidentified => i18n("Unidentified"),
+    }
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(resource = "/com/github/sixpounder/GameOfLife/census_panel.ui")]
+    pub struct GameOfLifeCensusPanel {
+        #[template_child]
+        pub(super) title: TemplateChild<adw::WindowTitle>,
+
+        #[template_child]
+        pub(super) stack: TemplateChild<gtk::Stack>,
+
+        #[template_child]
+        pub(super) table: TemplateChild<gtk::Grid>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for GameOfLifeCensusPanel {
+        const NAME: &'static str = "GameOfLifeCensusPanel";
+        type Type = super::GameOfLifeCensusPanel;
+        type ParentType = gtk::Widget;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+            klass.set_layout_manager_type::<gtk::BinLayout>();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for GameOfLifeCensusPanel {}
+    impl WidgetImpl for GameOfLifeCensusPanel {}
+}
+
+glib::wrapper! {
+    pub struct GameOfLifeCensusPanel(ObjectSubclass<imp::GameOfLifeCensusPanel>)
+        @extends gtk::Widget,
+        @implements gio::ActionGroup, gio::ActionMap, gtk::Root, gtk::Native, gtk::Buildable, gtk::ConstraintTarget, gtk::Accessible, gtk::ShortcutManager;
+}
+
+impl GameOfLifeCensusPanel {
+    /// Shows `census` as a table of the kinds of objects found and their count,
+    /// or tells that objects could not be counted when there is none
+    pub fn set_census(&self, census: Option<&Census>) {
+        let imp = self.imp();
+        let table = imp.table.get();
+        while let Some(child) = table.first_child() {
+            table.remove(&child);
+        }
+
+        let Some(census) = census else {
+            imp.title.set_subtitle("");
+            imp.stack.set_visible_child_name("unsupported");
+            return;
+        };
+
+        imp.title.set_subtitle(
+            &i18n("Objects: {count}").replace("{count}", &group_digits(census.objects() as u64)),
+        );
+        if census.is_empty() {
+            imp.stack.set_visible_child_name("empty");
+            return;
+        }
+
+        let headers = [i18n("Object"), i18n("Kind"), i18n("Count")];
+        for (column, header) in headers.iter().enumerate() {
+            let label = gtk::Label::builder()
+                .label(header)
+                .xalign(if column == 2 { 1.0 } else { 0.0 })
+                .css_classes(["heading"])
+                .build();
+            table.attach(&label, column as i32, 0, 1, 1);
+        }
+
+        for (index, entry) in census.entries().iter().enumerate() {
+            let row = index as i32 + 1;
+
+            // Objects go by their name when they have one, by their apgcode otherwise
+            let object = gtk::Label::builder()
+                .xalign(0.0)
+                .hexpand(true)
+                .ellipsize(gtk::pango::EllipsizeMode::End)
+                .selectable(true)
+                .build();
+            match (entry.name(), entry.code()) {
+                (Some(name), code) => {
+                    object.set_label(name);
+                    object.set_tooltip_text(code);
+                }
+                (None, Some(code)) => {
+                    object.set_label(code);
+                    object.set_tooltip_text(Some(code));
+                    object.add_css_class("monospace");
+                }
+                (None, None) => {
+                    object.set_label(&i18n("Other objects"));
+                    object.add_css_class("dim-label");
+                }
+            }
+            table.attach(&object, 0, row, 1, 1);
+
+            let kind = gtk::Label::builder()
+                .label(kind_label(entry.kind()))
+                .xalign(0.0)
+                .css_classes(["dim-label"])
+                .build();
+            table.attach(&kind, 1, row, 1, 1);
+
+            let count = gtk::Label::builder()
+                .label(group_digits(entry.count() as u64))
+                .xalign(1.0)
+                .css_classes(["numeric"])
+                .build();
+            table.attach(&count, 2, row, 1, 1);
+        }
+        imp.stack.set_visible_child_name("table");
+    }
+}
@@ -1,9 +1,15 @@
+mod census_panel;
 mod new_universe_view;
 mod preferences_window;
+mod statistics_panel;
+mod timeline;
 mod universe_controls;
 mod universe_grid;
 
+pub use census_panel::*;
 pub use new_universe_view::*;
 pub use preferences_window::*;
+pub use statistics_panel::*;
+pub use timeline::*;
 pub use universe_controls::*;
 pub use universe_grid::*;
@@ -1,4 +1,12 @@
-use crate::{config::G_LOG_DOMAIN, services::GameOfLifeSettings};
+use crate::{
+    config::G_LOG_DOMAIN,
+    i18n::i18n,
+    models::{
+        SoupOptions, SoupSymmetry, UniverseRule, UniverseTopology, UNIVERSE_MAX_SIDE,
+        UNIVERSE_RULE_PRESETS,
+    },
+    services::GameOfLifeSettings,
+};
 use gtk::{gio, glib, glib::clone};
 use gtk::{prelude::*, subclass::prelude::*, CompositeTemplate};
 
@@ -8,6 +16,7 @@ pub enum NewUniverseType {
     Empty,
     Random,
     Template(&'static str),
+    Wireworld,
 }
 
 impl Default for NewUniverseType {
@@ -33,9 +42,27 @@ mod imp {
         #[template_child]
         pub(super) random_check: TemplateChild<gtk::CheckButton>,
         #[template_child]
+        pub(super) soup_grid: TemplateChild<gtk::Grid>,
+        #[template_child]
+        pub(super) soup_seed_entry: TemplateChild<gtk::Entry>,
+        #[template_child]
+        pub(super) soup_density_entry: TemplateChild<gtk::SpinButton>,
+        #[template_child]
+        pub(super) soup_box_entry: TemplateChild<gtk::SpinButton>,
+        #[template_child]
+        pub(super) soup_symmetry_dropdown: TemplateChild<gtk::DropDown>,
+        #[template_child]
         pub(super) template_check: TemplateChild<gtk::CheckButton>,
         #[template_child]
         pub(super) template_list_dropdown: TemplateChild<gtk::DropDown>,
+        #[template_child]
+        pub(super) wireworld_check: TemplateChild<gtk::CheckButton>,
+        #[template_child]
+        pub(super) rule_entry: TemplateChild<gtk::Entry>,
+        #[template_child]
+        pub(super) rule_presets_dropdown: TemplateChild<gtk::DropDown>,
+        #[template_child]
+        pub(super) topology_dropdown: TemplateChild<gtk::DropDown>,
     }
 
     #[glib::object_subclass]
@@ -109,7 +136,7 @@ impl GameOfLifeNewUniverseView {
 
         let row_adjust = gtk::Adjustment::builder()
             .lower(10.)
-            .upper(1000.)
+            .upper(UNIVERSE_MAX_SIDE as f64)
             .step_increment(1.0)
             .page_increment(10.)
             .value(settings.universe_width().into())
@@ -117,7 +144,7 @@ impl GameOfLifeNewUniverseView {
 
         let column_adjust = gtk::Adjustment::builder()
             .lower(10.)
-            .upper(1000.)
+            .upper(UNIVERSE_MAX_SIDE as f64)
             .step_increment(1.0)
             .page_increment(10.)
             .value(settings.universe_height().into())
@@ -147,22 +174,312 @@ impl GameOfLifeNewUniverseView {
         self.imp().rows_entry.set_adjustment(&row_adjust);
         self.imp().columns_entry.set_adjustment(&column_adjust);
 
+        // Presets are followed by a "custom" entry, selected when the rule
+        // does not match any of them
+        let mut preset_names: Vec<String> = UNIVERSE_RULE_PRESETS
+            .iter()
+            .map(|(name, _)| i18n(name))
+            .collect();
+        preset_names.push(i18n("Custom"));
+        let preset_names: Vec<&str> = preset_names.iter().map(String::as_str).collect();
+        self.imp()
+            .rule_presets_dropdown
+            .set_model(Some(&gtk::StringList::new(&preset_names)));
+        self.imp()
+            .rule_entry
+            .set_text(settings.universe_rule().to_string().as_str());
+        self.sync_rule_widgets();
+
+        let topology_labels: Vec<String> = UniverseTopology::ALL
+            .iter()
+            .map(|topology| match topology {
+                UniverseTopology::DeadBoundary => i18n("Dead boundary"),
+                UniverseTopology::Torus => i18n("Torus"),
+                UniverseTopology::HorizontalCylinder => i18n("Horizontal cylinder"),
+                UniverseTopology::VerticalCylinder => i18n("Vertical cylinder"),
+                UniverseTopology::KleinBottle => i18n("Klein bottle"),
+                UniverseTopology::CrossSurface => i18n("Cross-surface"),
+                UniverseTopology::Unbounded => i18n("Unbounded plane"),
+            })
+            .collect();
+        let topology_labels: Vec<&str> = topology_labels.iter().map(String::as_str).collect();
+        self.imp()
+            .topology_dropdown
+            .set_model(Some(&gtk::StringList::new(&topology_labels)));
+        let topology_index = UniverseTopology::ALL
+            .iter()
+            .position(|topology| *topology == settings.universe_topology())
+            .unwrap_or_default();
+        self.imp()
+            .topology_dropdown
+            .set_selected(topology_index as u32);
+
+        let density_adjust = gtk::Adjustment::builder()
+            .lower(0.)
+            .upper(100.)
+            .step_increment(1.0)
+            .page_increment(10.)
+            .value((settings.soup_density() * 100.).round())
+            .build();
+
+        let box_adjust = gtk::Adjustment::builder()
+            .lower(0.)
+            .upper(UNIVERSE_MAX_SIDE as f64)
+            .step_increment(1.0)
+            .page_increment(10.)
+            .value(settings.soup_box().into())
+            .build();
+
+        density_adjust.connect_notify_local(
+            Some("value"),
+            clone!(
+                #[strong]
+                settings,
+                move |adj, _| {
+                    settings.set_soup_density(adj.value() / 100.);
+                }
+            ),
+        );
+
+        box_adjust.connect_notify_local(
+            Some("value"),
+            clone!(
+                #[strong]
+                settings,
+                move |adj, _| {
+                    settings.set_soup_box(adj.value() as u32);
+                }
+            ),
+        );
         self.imp()
-            .template_list_dropdown
-            .set_sensitive(self.imp().template_check.is_active());
+            .soup_density_entry
+            .set_adjustment(&density_adjust);
+        self.imp().soup_box_entry.set_adjustment(&box_adjust);
+
+        let symmetry_labels: Vec<String> = SoupSymmetry::ALL
+            .iter()
+            .map(|symmetry| match symmetry {
+                SoupSymmetry::None => i18n("None"),
+                SoupSymmetry::C2 => i18n("C2 (half turn)"),
+                SoupSymmetry::C4 => i18n("C4 (quarter turn)"),
+                SoupSymmetry::D8 => i18n("D8 (turns and reflections)"),
+            })
+            .collect();
+        let symmetry_labels: Vec<&str> = symmetry_labels.iter().map(String::as_str).collect();
+        self.imp()
+            .soup_symmetry_dropdown
+            .set_model(Some(&gtk::StringList::new(&symmetry_labels)));
+        let symmetry_index = SoupSymmetry::ALL
+            .iter()
+            .position(|symmetry| *symmetry == settings.soup_symmetry())
+            .unwrap_or_default();
+        self.imp()
+            .soup_symmetry_dropdown
+            .set_selected(symmetry_index as u32);
+
+        self.sync_option_widgets();
     }
 
     fn connect_events(&self) {
         self.imp().template_check.connect_toggled(clone!(
             #[strong(rename_to = this)]
             self,
-            move |widget| {
-                this.imp()
-                    .template_list_dropdown
-                    .set_sensitive(widget.is_active());
+            move |_| {
+                this.sync_option_widgets();
                 this.notify("dimensions-editable");
             }
         ));
+
+        self.imp().random_check.connect_toggled(clone!(
+            #[strong(rename_to = this)]
+            self,
+            move |_| {
+                this.sync_option_widgets();
+            }
+        ));
+
+        self.imp().wireworld_check.connect_toggled(clone!(
+            #[strong(rename_to = this)]
+            self,
+            move |_| {
+                this.sync_option_widgets();
+            }
+        ));
+
+        self.imp().soup_seed_entry.connect_changed(clone!(
+            #[strong(rename_to = this)]
+            self,
+            move |_| {
+                this.sync_rule_widgets();
+            }
+        ));
+
+        self.imp()
+            .soup_symmetry_dropdown
+            .connect_selected_notify(clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |_| {
+                    GameOfLifeSettings::default().set_soup_symmetry(this.soup_symmetry());
+                }
+            ));
+
+        self.imp().rule_entry.connect_changed(clone!(
+            #[strong(rename_to = this)]
+            self,
+            move |_| {
+                if let Some(rule) = this.rule() {
+                    GameOfLifeSettings::default().set_universe_rule(&rule);
+                }
+                this.sync_rule_widgets();
+            }
+        ));
+
+        self.imp()
+            .rule_presets_dropdown
+            .connect_selected_notify(clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |dropdown| {
+                    if let Some((_, rulestring)) =
+                        UNIVERSE_RULE_PRESETS.get(dropdown.selected() as usize)
+                    {
+                        let preset = rulestring.parse::<UniverseRule>().ok();
+                        if this.rule() != preset {
+                            this.imp().rule_entry.set_text(rulestring);
+                        }
+                    }
+                }
+            ));
+
+        self.imp().topology_dropdown.connect_selected_notify(clone!(
+            #[strong(rename_to = this)]
+            self,
+            move |_| {
+                GameOfLifeSettings::default().set_universe_topology(this.topology());
+                this.sync_rule_widgets();
+            }
+        ));
+
+        // Rules reaching farther than the universe wraps around do not fit it
+        for entry in [&self.imp().rows_entry, &self.imp().columns_entry] {
+            entry.connect_value_changed(clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |_| {
+                    this.sync_rule_widgets();
+                }
+            ));
+        }
+    }
+
+    /// Only lets the widgets the selected option makes use of be edited
+    fn sync_option_widgets(&self) {
+        let imp = self.imp();
+        let is_template = imp.template_check.is_active();
+        imp.template_list_dropdown.set_sensitive(is_template);
+        imp.soup_grid.set_sensitive(imp.random_check.is_active());
+
+        // Templates and Wireworld circuits carry their own rule and topology
+        let has_rule = !is_template && !imp.wireworld_check.is_active();
+        imp.rule_entry.set_sensitive(has_rule);
+        imp.rule_presets_dropdown.set_sensitive(has_rule);
+        imp.topology_dropdown.set_sensitive(has_rule);
+        self.sync_rule_widgets();
+    }
+
+    /// Reflects the validity of the typed rule and seed on their entries,
+    /// the preset selector and the accept button. Rules whose neighbourhood
+    /// is too wide for the size and the topology selected are not valid
+    fn sync_rule_widgets(&self) {
+        let imp = self.imp();
+        let rule = self.rule();
+        let (rows, columns) = self.size();
+        let topology = self.topology();
+        let rule_fits = rule.is_some_and(|rule| {
+            rule.range() <= topology.max_range(rows as usize, columns as usize)
+        });
+        let topology_fits = rule.is_none_or(|rule| topology.supports(&rule));
+
+        if rule_fits {
+            imp.rule_entry.remove_css_class("error");
+        } else {
+            imp.rule_entry.add_css_class("error");
+        }
+
+        if topology_fits {
+            imp.topology_dropdown.remove_css_class("error");
+            imp.topology_dropdown.set_tooltip_text(Some(&i18n(
+                "How the edges of the universe are joined together",
+            )));
+        } else {
+            imp.topology_dropdown.add_css_class("error");
+            imp.topology_dropdown.set_tooltip_text(Some(&i18n(
+                "Unbounded planes only take two states Life-like rules without births out of nothing",
+            )));
+        }
+
+        let preset_index = rule
+            .and_then(|rule| rule.preset_index())
+            .unwrap_or(UNIVERSE_RULE_PRESETS.len());
+        if imp.rule_presets_dropdown.selected() as usize != preset_index {
+            imp.rule_presets_dropdown.set_selected(preset_index as u32);
+        }
+
+        let seed_valid = self.soup_seed().is_ok();
+        if seed_valid {
+            imp.soup_seed_entry.remove_css_class("error");
+        } else {
+            imp.soup_seed_entry.add_css_class("error");
+        }
+
+        let can_accept = imp.template_check.is_active()
+            || imp.wireworld_check.is_active()
+            || (rule_fits && topology_fits && (seed_valid || !imp.random_check.is_active()));
+        self.set_response_sensitive(gtk::ResponseType::Ok, can_accept);
+    }
+
+    /// The rule typed in the dialog, if valid
+    pub fn rule(&self) -> Option<UniverseRule> {
+        self.imp().rule_entry.text().parse().ok()
+    }
+
+    /// The topology selected in the dialog
+    pub fn topology(&self) -> UniverseTopology {
+        UniverseTopology::ALL
+            .get(self.imp().topology_dropdown.selected() as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The seed typed in the dialog, `None` when left empty
+    fn soup_seed(&self) -> Result<Option<u64>, std::num::ParseIntError> {
+        let text = self.imp().soup_seed_entry.text();
+        match text.trim() {
+            "" => Ok(None),
+            seed => seed.parse().map(Some),
+        }
+    }
+
+    fn soup_symmetry(&self) -> SoupSymmetry {
+        SoupSymmetry::ALL
+            .get(self.imp().soup_symmetry_dropdown.selected() as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// How random universes are filled, with a seed drawn
+    /// at random when none is typed in the dialog
+    pub fn soup(&self) -> SoupOptions {
+        let imp = self.imp();
+        let mut soup = match self.soup_seed().ok().flatten() {
+            Some(seed) => SoupOptions::new(seed),
+            None => SoupOptions::random(),
+        };
+        soup.set_density(imp.soup_density_entry.value() / 100.);
+        soup.set_central_box(Some(imp.soup_box_entry.value() as usize));
+        soup.set_symmetry(self.soup_symmetry());
+        soup
     }
 
     pub fn option(&self) -> NewUniverseType {
@@ -170,6 +487,8 @@ impl GameOfLifeNewUniverseView {
             NewUniverseType::Empty
         } else if self.imp().random_check.is_active() {
             NewUniverseType::Random
+        } else if self.imp().wireworld_check.is_active() {
+            NewUniverseType::Wireworld
         } else {
             let selected_template_object = self
                 .imp()
@@ -190,6 +509,9 @@ impl GameOfLifeNewUniverseView {
                 "Circle of fire" => NewUniverseType::Template("circle_of_fire"),
                 "Quadpole" => NewUniverseType::Template("quadpole"),
                 "Spaceship" => NewUniverseType::Template("spaceship"),
+                "Diodes" => NewUniverseType::Template("diodes"),
+                "Clock" => NewUniverseType::Template("clock"),
+                "XOR gate" => NewUniverseType::Template("xor_gate"),
                 _ => unreachable!("This should not happen"),
             }
         }
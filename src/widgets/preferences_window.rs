@@ -1,4 +1,4 @@
-use crate::services::GameOfLifeSettings;
+use crate::{models::DecayCurve, services::GameOfLifeSettings};
 use adw::{
     subclass::{preferences_window::PreferencesWindowImpl, window::AdwWindowImpl},
     PreferencesWindow,
@@ -34,6 +34,15 @@ mod imp {
         #[template_child]
         pub(super) fade_out_dead_cells: TemplateChild<gtk::Switch>,
 
+        #[template_child]
+        pub(super) corpse_heat_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) corpse_decay_rate_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) corpse_decay_curve: TemplateChild<gtk::DropDown>,
+
         #[template_child]
         pub(super) allow_render_on_resize: TemplateChild<gtk::Switch>,
 
@@ -42,6 +51,21 @@ mod imp {
 
         #[template_child]
         pub(super) show_design_hint: TemplateChild<gtk::Switch>,
+
+        #[template_child]
+        pub(super) worker_threads: TemplateChild<gtk::SpinButton>,
+
+        #[template_child]
+        pub(super) worker_threads_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) history_depth: TemplateChild<gtk::SpinButton>,
+
+        #[template_child]
+        pub(super) history_depth_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) pause_on_stabilization: TemplateChild<gtk::Switch>,
     }
 
     #[glib::object_subclass]
@@ -168,6 +192,12 @@ impl GameOfLifePreferencesWindow {
             "active",
         );
         settings.bind("fade-out-cells", &imp.fade_out_dead_cells.get(), "active");
+        settings.bind("corpse-heat", &imp.corpse_heat_adjustment.get(), "value");
+        settings.bind(
+            "corpse-decay-rate",
+            &imp.corpse_decay_rate_adjustment.get(),
+            "value",
+        );
         settings.bind(
             "allow-render-during-resize",
             &imp.allow_render_on_resize.get(),
@@ -179,6 +209,21 @@ impl GameOfLifePreferencesWindow {
             &imp.evolution_speed_adjustment.get(),
             "value",
         );
+        settings.bind(
+            "worker-threads",
+            &imp.worker_threads_adjustment.get(),
+            "value",
+        );
+        settings.bind(
+            "history-depth",
+            &imp.history_depth_adjustment.get(),
+            "value",
+        );
+        settings.bind(
+            "pause-on-stabilization",
+            &imp.pause_on_stabilization.get(),
+            "active",
+        );
 
         // Proxy colors to this widget, to convert from RGBA to string
         settings.bind("fg-color", instance.as_ref(), "universe-cell-color");
@@ -194,6 +239,22 @@ impl GameOfLifePreferencesWindow {
             "universe-background-color-dark",
         );
 
+        // Decay curves are listed in the order of `DecayCurve::ALL`
+        let curve_index = DecayCurve::ALL
+            .iter()
+            .position(|curve| *curve == settings.corpse_decay_curve())
+            .unwrap_or_default();
+        imp.corpse_decay_curve.set_selected(curve_index as u32);
+        imp.corpse_decay_curve.connect_selected_notify(glib::clone!(
+            #[strong]
+            settings,
+            move |dropdown| {
+                if let Some(curve) = DecayCurve::ALL.get(dropdown.selected() as usize) {
+                    settings.set_corpse_decay_curve(*curve);
+                }
+            }
+        ));
+
         // Listen for color pickers
 
         imp.cell_color_picker.connect_color_set(glib::clone!(
@@ -0,0 +1,146 @@
+use super::timeline::draw_sparkline;
+use crate::i18n::{group_digits, i18n};
+use crate::models::GenerationStatistics;
+use gtk::{gio, glib, glib::clone};
+use gtk::{prelude::*, subclass::prelude::*, CompositeTemplate};
+use std::cell::RefCell;
+
+/// The number of generations the population chart goes back
+pub const STATISTICS_CHART_GENERATIONS: usize = 300;
+
+/// Writes a count, or a dash when it is not known
+fn count_label(count: Option<usize>) -> String {
+    count.map_or_else(|| String::from("—"), |count| group_digits(count as u64))
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(resource = "/com/github/sixpounder/GameOfLife/statistics_panel.ui")]
+    pub struct GameOfLifeStatisticsPanel {
+        #[template_child]
+        pub(super) chart: TemplateChild<gtk::DrawingArea>,
+
+        #[template_child]
+        pub(super) chart_caption: TemplateChild<gtk::Label>,
+
+        #[template_child]
+        pub(super) generation_label: TemplateChild<gtk::Label>,
+
+        #[template_child]
+        pub(super) population_label: TemplateChild<gtk::Label>,
+
+        #[template_child]
+        pub(super) births_label: TemplateChild<gtk::Label>,
+
+        #[template_child]
+        pub(super) deaths_label: TemplateChild<gtk::Label>,
+
+        #[template_child]
+        pub(super) density_label: TemplateChild<gtk::Label>,
+
+        #[template_child]
+        pub(super) bounding_box_label: TemplateChild<gtk::Label>,
+
+        /// The generation and the population of the generations charted
+        pub(super) series: RefCell<Vec<(u64, usize)>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for GameOfLifeStatisticsPanel {
+        const NAME: &'static str = "GameOfLifeStatisticsPanel";
+        type Type = super::GameOfLifeStatisticsPanel;
+        type ParentType = gtk::Widget;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+            klass.set_layout_manager_type::<gtk::BinLayout>();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for GameOfLifeStatisticsPanel {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            self.chart.set_draw_func(clone!(
+                #[weak]
+                obj,
+                move |area, context, width, height| {
+                    draw_sparkline(area, context, width, height, &obj.imp().series.borrow());
+                }
+            ));
+        }
+    }
+
+    impl WidgetImpl for GameOfLifeStatisticsPanel {}
+}
+
+glib::wrapper! {
+    pub struct GameOfLifeStatisticsPanel(ObjectSubclass<imp::GameOfLifeStatisticsPanel>)
+        @extends gtk::Widget,
+        @implements gio::ActionGroup, gio::ActionMap, gtk::Root, gtk::Native, gtk::Buildable, gtk::ConstraintTarget, gtk::Accessible, gtk::ShortcutManager;
+}
+
+impl GameOfLifeStatisticsPanel {
+    /// Shows `statistics`, those of the generation shown, and charts
+    /// `series`, the generation and the population of the last generations
+    pub fn set_statistics(
+        &self,
+        statistics: Option<GenerationStatistics>,
+        series: Vec<(u64, usize)>,
+    ) {
+        let imp = self.imp();
+
+        let caption = match (series.first(), series.last()) {
+            (Some((first, _)), Some((last, _))) if first != last => {
+                let peak = series.iter().map(|(_, population)| *population).max();
+                i18n("Generations {first} to {last}, peaking at {peak}")
+                    .replace("{first}", &group_digits(*first))
+                    .replace("{last}", &group_digits(*last))
+                    .replace("{peak}", &count_label(peak))
+            }
+            _ => String::new(),
+        };
+        imp.chart_caption.set_label(&caption);
+        imp.series.replace(series);
+        imp.chart.queue_draw();
+
+        imp.generation_label
+            .set_label(&statistics.map_or_else(String::new, |statistics| {
+                group_digits(statistics.generation())
+            }));
+        imp.population_label.set_label(&count_label(
+            statistics.map(|statistics| statistics.population()),
+        ));
+        imp.births_label.set_label(&count_label(
+            statistics.and_then(|statistics| statistics.births()),
+        ));
+        imp.deaths_label.set_label(&count_label(
+            statistics.and_then(|statistics| statistics.deaths()),
+        ));
+        imp.density_label
+            .set_label(&statistics.map_or_else(String::new, |statistics| {
+                format!("{:.2}%", statistics.density() * 100.0)
+            }));
+        imp.bounding_box_label.set_label(
+            &statistics
+                .and_then(|statistics| statistics.bounding_box_size())
+                .map_or_else(
+                    || String::from("—"),
+                    |(rows, columns)| {
+                        format!(
+                            "{} × {}",
+                            group_digits(rows as u64),
+                            group_digits(columns as u64)
+                        )
+                    },
+                ),
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use gtk::{gio, glib, glib::clone};
+use gtk::{prelude::*, subclass::prelude::*, CompositeTemplate};
+use std::cell::{Cell, RefCell};
+
+/// Draws the population of each generation of `timeline`
+/// as a line across an area of `width`x`height` pixels
+pub(crate) fn draw_sparkline(
+    area: &gtk::DrawingArea,
+    context: &gtk::cairo::Context,
+    width: i32,
+    height: i32,
+    timeline: &[(u64, usize)],
+) {
+    let max_population = timeline
+        .iter()
+        .map(|(_, population)| *population)
+        .max()
+        .unwrap_or_default();
+    if timeline.len() < 2 || max_population == 0 {
+        return;
+    }
+
+    let (width, height) = (width as f64, height as f64);
+    let step = width / (timeline.len() - 1) as f64;
+    let point = |index: usize, population: usize| {
+        (
+            index as f64 * step,
+            height - (population as f64 / max_population as f64) * (height - 1.0),
+        )
+    };
+
+    context.move_to(0.0, height);
+    for (index, (_, population)) in timeline.iter().enumerate() {
+        let (x, y) = point(index, *population);
+        context.line_to(x, y);
+    }
+    context.line_to(width, height);
+    context.close_path();
+
+    let color = area.style_context().color();
+    context.set_source_rgba(
+        color.red() as f64,
+        color.green() as f64,
+        color.blue() as f64,
+        color.alpha() as f64 * 0.15,
+    );
+    let _ = context.fill();
+
+    for (index, (_, population)) in timeline.iter().enumerate() {
+        let (x, y) = point(index, *population);
+        context.line_to(x, y);
+    }
+    context.set_source_rgba(
+        color.red() as f64,
+        color.green() as f64,
+        color.blue() as f64,
+        color.alpha() as f64 * 0.6,
+    );
+    context.set_line_width(1.0);
+    let _ = context.stroke();
+}
+
+mod imp {
+    use super::*;
+    use glib::{ParamSpec, ParamSpecUInt};
+    use once_cell::sync::Lazy;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(resource = "/com/github/sixpounder/GameOfLife/timeline.ui")]
+    pub struct GameOfLifeTimeline {
+        #[template_child]
+        pub(super) sparkline: TemplateChild<gtk::DrawingArea>,
+
+        #[template_child]
+        pub(super) scale: TemplateChild<gtk::Scale>,
+
+        #[template_child]
+        pub(super) adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) generation_label: TemplateChild<gtk::Label>,
+
+        /// The generation and the population at each position
+        pub(super) timeline: RefCell<Vec<(u64, usize)>>,
+
+        /// Set while the timeline is being replaced, so that
+        /// only moves made by the user notify a new position
+        pub(super) updating: Cell<bool>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for GameOfLifeTimeline {
+        const NAME: &'static str = "GameOfLifeTimeline";
+        type Type = super::GameOfLifeTimeline;
+        type ParentType = gtk::Widget;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+            klass.set_layout_manager_type::<gtk::BinLayout>();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for GameOfLifeTimeline {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            self.sparkline.set_draw_func(clone!(
+                #[weak]
+                obj,
+                move |area, context, width, height| {
+                    draw_sparkline(area, context, width, height, &obj.imp().timeline.borrow());
+                }
+            ));
+
+            self.adjustment.connect_value_changed(clone!(
+                #[weak]
+                obj,
+                move |_| {
+                    obj.sync_generation_label();
+                    if !obj.imp().updating.get() {
+                        obj.notify("position");
+                    }
+                }
+            ));
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            static PROPERTIES: Lazy<Vec<ParamSpec>> = Lazy::new(|| {
+                vec![ParamSpecUInt::builder("position")
+                    .default_value(0)
+                    .read_only()
+                    .build()]
+            });
+            PROPERTIES.as_ref()
+        }
+
+        fn property(&self, _id: usize, pspec: &ParamSpec) -> glib::Value {
+            match pspec.name() {
+                "position" => (self.obj().position() as u32).to_value(),
+                _ => unimplemented!(),
+            }
+        }
+    }
+
+    impl WidgetImpl for GameOfLifeTimeline {}
+}
+
+glib::wrapper! {
+    pub struct GameOfLifeTimeline(ObjectSubclass<imp::GameOfLifeTimeline>)
+        @extends gtk::Widget,
+        @implements gio::ActionGroup, gio::ActionMap, gtk::Root, gtk::Native, gtk::Buildable, gtk::ConstraintTarget, gtk::Accessible, gtk::ShortcutManager;
+}
+
+impl GameOfLifeTimeline {
+    /// Gets the position the slider is at
+    pub fn position(&self) -> usize {
+        self.imp().adjustment.value().round().max(0.0) as usize
+    }
+
+    /// Replaces the generations covered by the slider with `timeline`,
+    /// the generation and the population at each position, and moves the
+    /// slider to `position`. Does not notify a new position
+    pub fn set_timeline(&self, timeline: Vec<(u64, usize)>, position: usize) {
+        let imp = self.imp();
+        let last = timeline.len().saturating_sub(1);
+        imp.timeline.replace(timeline);
+
+        imp.updating.set(true);
+        imp.adjustment.set_upper(last as f64);
+        imp.adjustment.set_value(position.min(last) as f64);
+        imp.updating.set(false);
+
+        self.sync_generation_label();
+        imp.sparkline.queue_draw();
+    }
+
+    /// Shows the generation at the position the slider is at
+    fn sync_generation_label(&self) {
+        let imp = self.imp();
+        let generation = imp
+            .timeline
+            .borrow()
+            .get(self.position())
+            .map(|(generation, _)| generation.to_string())
+            .unwrap_or_default();
+        imp.generation_label.set_label(&generation);
+    }
+}
@@ -1,10 +1,10 @@
 use glib::prelude::*;
-use gtk::{gio, glib};
+use gtk::{gio, glib, glib::clone};
 use gtk::{prelude::*, subclass::prelude::*, CompositeTemplate};
 
 mod imp {
     use super::*;
-    use glib::{ParamSpec, ParamSpecBoolean, ParamSpecString};
+    use glib::{ParamSpec, ParamSpecBoolean, ParamSpecString, ParamSpecUInt};
     use once_cell::sync::Lazy;
 
     #[derive(Debug, Default, CompositeTemplate)]
@@ -16,9 +16,19 @@ mod imp {
         #[template_child]
         pub(super) random_seed_button: TemplateChild<gtk::Button>,
 
+        #[template_child]
+        pub(super) seed_box: TemplateChild<gtk::Box>,
+
+        #[template_child]
+        pub(super) palette_dropdown: TemplateChild<gtk::DropDown>,
+
+        #[template_child]
+        pub(super) palette_model: TemplateChild<gtk::StringList>,
+
         pub(super) playing: std::cell::Cell<bool>,
         pub(super) reveal_tools: std::cell::Cell<bool>,
         pub(super) brush_mode: std::cell::Cell<bool>,
+        pub(super) seed: std::cell::RefCell<String>,
     }
 
     #[glib::object_subclass]
@@ -38,6 +48,16 @@ mod imp {
     }
 
     impl ObjectImpl for GameOfLifeUniverseControls {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+            self.palette_dropdown.connect_selected_notify(clone!(
+                #[weak]
+                obj,
+                move |_| obj.notify("brush-state")
+            ));
+        }
+
         fn properties() -> &'static [ParamSpec] {
             static PROPERTIES: Lazy<Vec<ParamSpec>> = Lazy::new(|| {
                 vec![
@@ -57,6 +77,16 @@ mod imp {
                         .default_value(false)
                         .readwrite()
                         .build(),
+                    ParamSpecString::builder("seed")
+                        .default_value(Some(""))
+                        .readwrite()
+                        .build(),
+                    ParamSpecUInt::builder("brush-state")
+                        .minimum(1)
+                        .maximum(u8::MAX.into())
+                        .default_value(1)
+                        .readwrite()
+                        .build(),
                     ParamSpecString::builder("run-button-icon-name")
                         .default_value(Some("media-playback-start-symbolic"))
                         .readwrite()
@@ -74,6 +104,10 @@ mod imp {
                 "stopped" => (!imp.playing.get()).to_value(),
                 "reveal-tools" => imp.reveal_tools.get().to_value(),
                 "brush-mode" => imp.brush_mode.get().to_value(),
+                "seed" => imp.seed.borrow().to_value(),
+                "brush-state" => {
+                    (imp.palette_dropdown.selected().min(u8::MAX.into() - 1) + 1).to_value()
+                }
                 "run-button-icon-name" => match obj.property("playing") {
                     true => "media-playback-stop-symbolic",
                     false => "media-playback-start-symbolic",
@@ -111,6 +145,20 @@ mod imp {
                 "reveal-tools" => {
                     obj.imp().reveal_tools.set(value.get::<bool>().unwrap());
                 }
+                "seed" => {
+                    let seed = value.get::<Option<String>>().unwrap().unwrap_or_default();
+                    // Universes not drawn from a seed have nothing to show
+                    self.seed_box.set_visible(!seed.is_empty());
+                    self.seed.replace(seed);
+                    obj.notify("seed");
+                }
+                "brush-state" => {
+                    let state = value.get::<u32>().unwrap_or(1).max(1);
+                    // The first entry of the palette is the first non empty state
+                    if state - 1 < self.palette_model.n_items() {
+                        self.palette_dropdown.set_selected(state - 1);
+                    }
+                }
                 _ => unimplemented!(),
             }
         }
@@ -149,4 +197,15 @@ impl GameOfLifeUniverseControls {
     pub fn brush(&self) -> bool {
         self.imp().brush_mode.get()
     }
+
+    /// Fills the palette with the names of the states cells can be painted with.
+    /// The palette is only shown when there is more than one to choose from
+    pub fn set_palette(&self, names: &[String]) {
+        let imp = self.imp();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        imp.palette_model
+            .splice(0, imp.palette_model.n_items(), names.as_slice());
+        imp.palette_dropdown.set_selected(0);
+        imp.palette_dropdown.set_visible(names.len() > 1);
+    }
 }
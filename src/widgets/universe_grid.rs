@@ -1,35 +1,235 @@
 use crate::config::G_LOG_DOMAIN;
+use crate::i18n::i18n;
 use crate::models::{
-    Universe, UniverseCell, UniverseGridMode, UniversePoint, UniversePointMatrix, UniverseSnapshot,
+    CellularAutomaton, Census, CorpseDecay, GenerationStatistics, Motion, MotionError, ObjectCells,
+    Stabilization, StabilizationDetector, StateColor, StatisticsFormat, StatisticsRecord, Universe,
+    UniverseGridMode, UniverseHistory, UniverseNeighbourhood, UniverseSnapshot,
 };
 use crate::services::GameOfLifeSettings;
 use gtk::{gio, glib::clone, prelude::*, subclass::prelude::*, CompositeTemplate};
 
 use std::cell::{Cell, RefCell};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
 use async_channel::{Receiver, Sender};
 
-/// Maps a point on the widget area onto a cell in a given universe
+/// The layout of the cells of a universe on the widget area
+#[derive(Debug, Clone, Copy)]
+struct GridGeometry {
+    neighbourhood: UniverseNeighbourhood,
+    rows: usize,
+    columns: usize,
+
+    /// The width of a cell (for triangles, half of their base)
+    cell_width: f64,
+
+    /// The height of a cell
+    cell_height: f64,
+}
+
+impl GridGeometry {
+    fn new(universe: &dyn CellularAutomaton, area_width: f64, area_height: f64) -> Self {
+        let neighbourhood = universe.neighbourhood();
+        let (rows, columns) = (universe.rows(), universe.columns());
+        let (cell_width, cell_height) = match neighbourhood {
+            // Odd rows are shifted by half a cell, and rows overlap by a quarter of a cell
+            UniverseNeighbourhood::Hexagonal => (
+                area_width / (columns as f64 + 0.5),
+                area_height / (0.75 * rows as f64 + 0.25),
+            ),
+            // Adjacent triangles overlap by half of their base
+            UniverseNeighbourhood::Triangular => (
+                area_width / (columns as f64 + 1.0),
+                area_height / rows as f64,
+            ),
+            _ => (area_width / columns as f64, area_height / rows as f64),
+        };
+
+        Self {
+            neighbourhood,
+            rows,
+            columns,
+            cell_width,
+            cell_height,
+        }
+    }
+
+    fn is_square(&self) -> bool {
+        self.neighbourhood.grid_offsets(0, 0).is_none()
+    }
+
+    /// Gets the bounding box of the cell at `row`x`column`
+    fn cell_bounds(&self, row: usize, column: usize) -> gtk::graphene::Rect {
+        let (x, y, width) = match self.neighbourhood {
+            UniverseNeighbourhood::Hexagonal => (
+                (column as f64 + 0.5 * (row % 2) as f64) * self.cell_width,
+                0.75 * row as f64 * self.cell_height,
+                self.cell_width,
+            ),
+            UniverseNeighbourhood::Triangular => (
+                column as f64 * self.cell_width,
+                row as f64 * self.cell_height,
+                2.0 * self.cell_width,
+            ),
+            _ => (
+                column as f64 * self.cell_width,
+                row as f64 * self.cell_height,
+                self.cell_width,
+            ),
+        };
+
+        gtk::graphene::Rect::new(x as f32, y as f32, width as f32, self.cell_height as f32)
+    }
+
+    /// Gets the corners of the cell at `row`x`column`
+    fn cell_vertices(&self, row: usize, column: usize) -> Vec<(f64, f64)> {
+        let bounds = self.cell_bounds(row, column);
+        let (x, y) = (bounds.x() as f64, bounds.y() as f64);
+        let (w, h) = (bounds.width() as f64, bounds.height() as f64);
+        match self.neighbourhood {
+            UniverseNeighbourhood::Hexagonal => vec![
+                (x + w / 2.0, y),
+                (x + w, y + h / 4.0),
+                (x + w, y + 3.0 * h / 4.0),
+                (x + w / 2.0, y + h),
+                (x, y + 3.0 * h / 4.0),
+                (x, y + h / 4.0),
+            ],
+            UniverseNeighbourhood::Triangular if (row + column).is_multiple_of(2) => {
+                vec![(x + w / 2.0, y), (x + w, y + h), (x, y + h)]
+            }
+            UniverseNeighbourhood::Triangular => vec![(x, y), (x + w, y), (x + w / 2.0, y + h)],
+            _ => vec![(x, y), (x + w, y), (x + w, y + h), (x, y + h)],
+        }
+    }
+
+    /// Adds the outline of the cell at `row`x`column` to the current path of `context`
+    fn trace_cell(&self, context: &gtk::cairo::Context, row: usize, column: usize) {
+        let vertices = self.cell_vertices(row, column);
+        context.move_to(vertices[0].0, vertices[0].1);
+        for (x, y) in vertices.iter().skip(1) {
+            context.line_to(*x, *y);
+        }
+        context.close_path();
+    }
+
+    /// Converts a move by `x` and `y` pixels into the number of rows and columns it spans
+    fn cells_in_offset(&self, x: f64, y: f64) -> (i64, i64) {
+        let row_height = match self.neighbourhood {
+            UniverseNeighbourhood::Hexagonal => 0.75 * self.cell_height,
+            _ => self.cell_height,
+        };
+        (
+            (y / row_height).round() as i64,
+            (x / self.cell_width).round() as i64,
+        )
+    }
+
+    /// Finds the cell containing the point at `x` and `y` coordinates
+    fn cell_at(&self, x: f64, y: f64) -> Option<(usize, usize)> {
+        if x < 0.0 || y < 0.0 {
+            return None;
+        }
+
+        let candidates = |row_guess: f64, column_guess: f64| {
+            let (row_guess, column_guess) =
+                (row_guess.floor() as isize, column_guess.floor() as isize);
+            (row_guess - 1..=row_guess + 1)
+                .flat_map(move |row| {
+                    (column_guess - 1..=column_guess + 1).map(move |column| (row, column))
+                })
+                .filter(|(row, column)| {
+                    (0..self.rows as isize).contains(row)
+                        && (0..self.columns as isize).contains(column)
+                })
+                .map(|(row, column)| (row as usize, column as usize))
+        };
+
+        match self.neighbourhood {
+            // The closest cell center is the hexagon containing the point
+            UniverseNeighbourhood::Hexagonal => {
+                candidates(y / (0.75 * self.cell_height), x / self.cell_width)
+                    .map(|(row, column)| {
+                        let bounds = self.cell_bounds(row, column);
+                        let center = bounds.center();
+                        let distance = ((x - center.x() as f64) / self.cell_width).powi(2)
+                            + ((y - center.y() as f64) / self.cell_height).powi(2);
+                        (distance, (row, column))
+                    })
+                    .min_by(|a, b| a.0.total_cmp(&b.0))
+                    .map(|(_, cell)| cell)
+            }
+            UniverseNeighbourhood::Triangular => {
+                candidates(y / self.cell_height, x / self.cell_width).find(|(row, column)| {
+                    if *row != (y / self.cell_height).floor() as usize {
+                        return false;
+                    }
+
+                    // The point is inside if it lies on the same side of all the edges
+                    let vertices = self.cell_vertices(*row, *column);
+                    let sides: Vec<bool> = (0..3)
+                        .map(|i| {
+                            let (ax, ay) = vertices[i];
+                            let (bx, by) = vertices[(i + 1) % 3];
+                            (bx - ax) * (y - ay) - (by - ay) * (x - ax) >= 0.0
+                        })
+                        .collect();
+                    sides.iter().all(|side| *side) || sides.iter().all(|side| !*side)
+                })
+            }
+            _ => {
+                let (row, column) = (
+                    (y / self.cell_height) as usize,
+                    (x / self.cell_width) as usize,
+                );
+                (row < self.rows && column < self.columns).then_some((row, column))
+            }
+        }
+    }
+}
+
+/// Maps a point on the widget area onto the row and the column of a cell in a given universe
 fn widget_area_point_to_universe_cell(
     drawing_area: &GameOfLifeUniverseGrid,
-    universe: Option<&Universe>,
+    universe: Option<&dyn CellularAutomaton>,
     x: f64,
     y: f64,
-) -> Option<UniversePoint> {
+) -> Option<(usize, usize)> {
     if let Some(universe) = universe {
         let (widget_width, widget_height) = (drawing_area.width(), drawing_area.height());
-        let (universe_width, universe_height) = (universe.rows(), universe.columns());
-
-        let universe_row = ((x.round() as i32) * universe_width as i32) / widget_width;
-        let universe_column = ((y.round() as i32) * universe_height as i32) / widget_height;
+        let geometry = GridGeometry::new(universe, widget_width as f64, widget_height as f64);
 
-        universe.get(universe_row as usize, universe_column as usize)
+        geometry.cell_at(x, y)
     } else {
         None
     }
 }
 
+/// Resolves how cells are painted into an actual color, out of the
+/// cell and background colors. `None` means cells are not painted at all
+fn cell_state_color(
+    fg_color: &gtk::gdk::RGBA,
+    bg_color: &gtk::gdk::RGBA,
+    color: StateColor,
+) -> Option<gtk::gdk::RGBA> {
+    match color {
+        StateColor::Background => None,
+        StateColor::Foreground => Some(*fg_color),
+        StateColor::Faded(factor) => {
+            let blend = |from: f32, to: f32| from + (to - from) * factor;
+            Some(gtk::gdk::RGBA::new(
+                blend(fg_color.red(), bg_color.red()),
+                blend(fg_color.green(), bg_color.green()),
+                blend(fg_color.blue(), bg_color.blue()),
+                blend(fg_color.alpha(), bg_color.alpha()),
+            ))
+        }
+        StateColor::Fixed(red, green, blue) => Some(gtk::gdk::RGBA::new(red, green, blue, 1.0)),
+    }
+}
+
 fn snapshot_grid(
     widget: &imp::GameOfLifeUniverseGrid,
     snapshot: &gtk::Snapshot,
@@ -54,48 +254,52 @@ fn snapshot_grid(
 
     // Get a lock on the universe object
     let universe = widget.universe.borrow();
-    if let Some(universe) = universe.as_ref() {
-        let (width, height) = (
-            bounds.width() as f64 / universe.columns() as f64,
-            bounds.height() as f64 / universe.rows() as f64,
-        );
-
-        for el in universe.iter_cells() {
-            let w = el.row();
-            let h = el.column();
-            let coords: (f64, f64) = ((w as f64) * width, (h as f64) * height);
-
-            if wants_outlines {
-                cairo_context.rectangle(coords.0, coords.1, width, height);
-                cairo_context.set_line_width(1.0);
+    if let Some(universe) = universe.as_deref() {
+        let geometry = GridGeometry::new(universe, bounds.width() as f64, bounds.height() as f64);
+
+        let state_colors: Vec<Option<gtk::gdk::RGBA>> = (0..universe.states())
+            .map(|state| cell_state_color(&fg_color, &bg_color, universe.state_color(state)))
+            .collect();
+
+        // Square cells are appended as color nodes, other shapes are filled with cairo
+        let fill_cell = |row: usize, column: usize, color: &gtk::gdk::RGBA| {
+            if geometry.is_square() {
+                snapshot.append_color(color, &geometry.cell_bounds(row, column));
+            } else {
+                geometry.trace_cell(&cairo_context, row, column);
                 cairo_context.set_source_rgba(
-                    outline_color.red() as f64,
-                    outline_color.green() as f64,
-                    outline_color.blue() as f64,
-                    outline_color.alpha() as f64,
-                );
-                cairo_context.stroke().unwrap();
-            }
-            if el.cell().is_alive() {
-                let cell_rect_bounds = gtk::graphene::Rect::new(
-                    coords.0 as f32,
-                    coords.1 as f32,
-                    width as f32,
-                    height as f32,
+                    color.red() as f64,
+                    color.green() as f64,
+                    color.blue() as f64,
+                    color.alpha() as f64,
                 );
-                snapshot.append_color(&fg_color, &cell_rect_bounds);
-            } else if fades_dead_cells {
-                let transparency_factor = el.corpse_heat();
-                if transparency_factor > 0.0 {
-                    let cell_rect_bounds = gtk::graphene::Rect::new(
-                        coords.0 as f32,
-                        coords.1 as f32,
-                        width as f32,
-                        height as f32,
+                cairo_context.fill().unwrap();
+            }
+        };
+
+        for row in 0..universe.rows() {
+            for column in 0..universe.columns() {
+                if wants_outlines {
+                    geometry.trace_cell(&cairo_context, row, column);
+                    cairo_context.set_line_width(1.0);
+                    cairo_context.set_source_rgba(
+                        outline_color.red() as f64,
+                        outline_color.green() as f64,
+                        outline_color.blue() as f64,
+                        outline_color.alpha() as f64,
                     );
-                    let mut fade_color = fg_color;
-                    fade_color.set_alpha(fade_color.alpha() * transparency_factor as f32);
-                    snapshot.append_color(&fade_color, &cell_rect_bounds);
+                    cairo_context.stroke().unwrap();
+                }
+                let state = universe.state(row, column);
+                if let Some(color) = state_colors.get(state as usize).copied().flatten() {
+                    fill_cell(row, column, &color);
+                } else if state == 0 && fades_dead_cells {
+                    let transparency_factor = universe.corpse_heat(row, column);
+                    if transparency_factor > 0.0 {
+                        let mut fade_color = fg_color;
+                        fade_color.set_alpha(fade_color.alpha() * transparency_factor as f32);
+                        fill_cell(row, column, &fade_color);
+                    }
                 }
             }
         }
@@ -112,7 +316,15 @@ pub enum UniverseGridRequest {
 
     /// Requests the grid to redraw itself. If the value is Some(universe) the contained
     /// value will replace the current model inside the widget
-    Redraw(Option<Universe>),
+    Redraw(Option<Box<dyn CellularAutomaton>>),
+
+    /// Carries the universe a skip forward ended with, along with the
+    /// cancellation flag of the job that computed it
+    Skipped(Box<dyn CellularAutomaton>, Arc<AtomicBool>),
+
+    /// Carries a census taken off the main thread, along with
+    /// the number of the request it answers
+    Counted(Option<Census>, u64),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -130,7 +342,9 @@ impl Default for UniverseGridInteractionState {
 
 mod imp {
     use super::*;
-    use glib::{ParamSpec, ParamSpecBoolean, ParamSpecEnum, ParamSpecUInt};
+    use glib::{
+        ParamSpec, ParamSpecBoolean, ParamSpecEnum, ParamSpecString, ParamSpecUInt, ParamSpecUInt64,
+    };
     use once_cell::sync::Lazy;
 
     #[derive(Debug, Default, CompositeTemplate)]
@@ -142,7 +356,7 @@ mod imp {
 
         pub(super) mode: Cell<UniverseGridMode>,
 
-        pub(super) universe: RefCell<Option<Universe>>,
+        pub(super) universe: RefCell<Option<Box<dyn CellularAutomaton>>>,
 
         pub(super) receiver: RefCell<Option<Receiver<UniverseGridRequest>>>,
 
@@ -156,10 +370,13 @@ mod imp {
 
         pub(super) bg_color: Cell<Option<gtk::gdk::RGBA>>,
 
-        pub(super) point_under_pointing_device: Cell<Option<UniversePoint>>,
+        /// Row and column of the cell under the pointer
+        pub(super) point_under_pointing_device: Cell<Option<(usize, usize)>>,
 
         pub(super) evolution_speed: Cell<u32>,
 
+        pub(super) worker_threads: Cell<u32>,
+
         pub(super) animated: Cell<bool>,
 
         pub(super) draw_cells_outline: Cell<bool>,
@@ -167,6 +384,54 @@ mod imp {
         pub(super) fades_dead_cells: Cell<bool>,
 
         pub(super) interaction_state: Cell<UniverseGridInteractionState>,
+
+        /// Rows and columns the viewport has been moved by during the current drag
+        pub(super) drag_pan: Cell<(i64, i64)>,
+
+        /// Viewport moves yet to be applied by the ticking thread
+        pub(super) pending_pan: Arc<Mutex<(i64, i64)>>,
+
+        /// Decay of dead cells yet to be applied by the ticking thread
+        pub(super) pending_corpse_decay: Arc<Mutex<Option<CorpseDecay>>>,
+
+        /// The soup seed of the universe last shown
+        pub(super) seed: Cell<Option<u64>>,
+
+        /// The number of states of the universe last shown
+        pub(super) states: Cell<u8>,
+
+        /// The state cells are painted with by the primary button
+        pub(super) brush_state: Cell<u8>,
+
+        /// Past generations of the universe shown, to step back to
+        pub(super) history: RefCell<UniverseHistory>,
+
+        /// The generation of the universe last shown
+        pub(super) generation: Cell<u64>,
+
+        /// Watches the universe shown for it repeating itself
+        pub(super) stabilization_detector: RefCell<StabilizationDetector>,
+
+        /// How the universe shown stabilized, once it did
+        pub(super) stabilization: Cell<Option<Stabilization>>,
+
+        pub(super) pauses_on_stabilization: Cell<bool>,
+
+        /// Statistics of the generations shown
+        pub(super) statistics: RefCell<StatisticsRecord>,
+
+        /// The cancellation flag of the skip forward being computed, if any
+        pub(super) skipping: RefCell<Option<Arc<AtomicBool>>>,
+
+        /// The census of the universe shown, once taken
+        pub(super) census: RefCell<Option<Census>>,
+
+        /// The number of the last census requested, counts answering
+        /// earlier ones being taken again rather than shown
+        pub(super) census_request: Cell<u64>,
+
+        /// Whether a census is being taken
+        pub(super) counting: Cell<bool>,
     }
 
     #[glib::object_subclass]
@@ -181,10 +446,18 @@ mod imp {
 
             let mut this = Self::default();
 
-            this.universe.replace(Some(Universe::new_random(
+            let mut universe = Universe::new_soup(
                 this.settings.universe_width() as usize,
                 this.settings.universe_height() as usize,
-            )));
+                this.settings.soup_options(),
+            );
+            this.seed.set(universe.soup().map(|soup| soup.seed()));
+            universe.set_rule(this.settings.universe_rule());
+            universe.set_topology(this.settings.universe_topology());
+            universe.set_corpse_decay(this.settings.corpse_decay());
+            this.states.set(universe.rule().states());
+            this.brush_state.set(1);
+            this.universe.replace(Some(Box::new(universe)));
 
             this.receiver = receiver;
             this.sender = Some(sender);
@@ -251,6 +524,58 @@ mod imp {
                         .default_value(5)
                         .readwrite()
                         .build(),
+                    ParamSpecString::builder("seed")
+                        .default_value(Some(""))
+                        .read_only()
+                        .build(),
+                    ParamSpecUInt::builder("states")
+                        .minimum(2)
+                        .maximum(u8::MAX.into())
+                        .default_value(2)
+                        .read_only()
+                        .build(),
+                    ParamSpecUInt::builder("brush-state")
+                        .minimum(1)
+                        .maximum(u8::MAX.into())
+                        .default_value(1)
+                        .readwrite()
+                        .build(),
+                    ParamSpecBoolean::builder("can-step-back")
+                        .default_value(false)
+                        .read_only()
+                        .build(),
+                    ParamSpecUInt::builder("history-length")
+                        .default_value(0)
+                        .read_only()
+                        .build(),
+                    ParamSpecUInt::builder("history-position")
+                        .default_value(0)
+                        .read_only()
+                        .build(),
+                    ParamSpecUInt64::builder("generation")
+                        .default_value(0)
+                        .read_only()
+                        .build(),
+                    ParamSpecBoolean::builder("stabilized")
+                        .default_value(false)
+                        .read_only()
+                        .build(),
+                    ParamSpecBoolean::builder("pauses-on-stabilization")
+                        .default_value(false)
+                        .readwrite()
+                        .build(),
+                    ParamSpecUInt64::builder("population")
+                        .default_value(0)
+                        .read_only()
+                        .build(),
+                    ParamSpecBoolean::builder("skipping-forward")
+                        .default_value(false)
+                        .read_only()
+                        .build(),
+                    ParamSpecBoolean::builder("census")
+                        .default_value(false)
+                        .read_only()
+                        .build(),
                 ]
             });
             PROPERTIES.as_ref()
@@ -277,6 +602,12 @@ mod imp {
                 "evolution-speed" => {
                     obj.set_evolution_speed(value.get::<u32>().unwrap_or(5));
                 }
+                "brush-state" => {
+                    obj.set_brush_state(value.get::<u32>().unwrap_or(1) as u8);
+                }
+                "pauses-on-stabilization" => {
+                    obj.set_pauses_on_stabilization(value.get::<bool>().unwrap());
+                }
                 _ => unimplemented!(),
             }
         }
@@ -291,6 +622,26 @@ mod imp {
                 "animated" => obj.animated().to_value(),
                 "evolution-speed" => obj.evolution_speed().to_value(),
                 "running" => obj.is_running().to_value(),
+                "seed" => self
+                    .seed
+                    .get()
+                    .map(|seed| seed.to_string())
+                    .unwrap_or_default()
+                    .to_value(),
+                "states" => u32::from(self.states.get()).to_value(),
+                "brush-state" => u32::from(obj.brush_state()).to_value(),
+                "can-step-back" => obj.can_step_back().to_value(),
+                "history-length" => (obj.history_length() as u32).to_value(),
+                "history-position" => (obj.history_position() as u32).to_value(),
+                "generation" => self.generation.get().to_value(),
+                "stabilized" => obj.stabilization().is_some().to_value(),
+                "pauses-on-stabilization" => obj.pauses_on_stabilization().to_value(),
+                "population" => obj
+                    .statistics()
+                    .map_or(0, |statistics| statistics.population() as u64)
+                    .to_value(),
+                "skipping-forward" => obj.is_skipping_forward().to_value(),
+                "census" => self.census.borrow().is_some().to_value(),
                 _ => unimplemented!(),
             }
         }
@@ -344,7 +695,8 @@ impl GameOfLifeUniverseGrid {
             #[strong(rename_to = this)]
             self,
             move |gesture, n_press, x, y| {
-                this.on_drawing_area_clicked(gesture, n_press, x, y, Some(UniverseCell::Alive));
+                let state = this.brush_state();
+                this.on_drawing_area_clicked(gesture, n_press, x, y, Some(state));
             }
         ));
         left_click_gesture_controller.connect_released(clone!(
@@ -369,7 +721,7 @@ impl GameOfLifeUniverseGrid {
             #[strong(rename_to = this)]
             self,
             move |gesture, n_press, x, y| {
-                this.on_drawing_area_clicked(gesture, n_press, x, y, Some(UniverseCell::Dead));
+                this.on_drawing_area_clicked(gesture, n_press, x, y, Some(0));
             }
         ));
         right_click_gesture_controller.connect_released(clone!(
@@ -394,7 +746,8 @@ impl GameOfLifeUniverseGrid {
             #[strong(rename_to = this)]
             self,
             move |gesture, events| {
-                this.on_drawing_area_drag_begin(gesture, events, Some(UniverseCell::Alive))
+                let state = this.brush_state();
+                this.on_drawing_area_drag_begin(gesture, events, Some(state))
             }
         ));
 
@@ -402,7 +755,8 @@ impl GameOfLifeUniverseGrid {
             #[strong(rename_to = this)]
             self,
             move |gesture, events| {
-                this.on_drawing_area_drag_move(gesture, events, Some(UniverseCell::Alive))
+                let state = this.brush_state();
+                this.on_drawing_area_drag_move(gesture, events, Some(state))
             }
         ));
         drawing_area.add_controller(left_drag_gesture_controller);
@@ -412,17 +766,13 @@ impl GameOfLifeUniverseGrid {
         right_drag_gesture_controller.connect_begin(clone!(
             #[strong(rename_to = this)]
             self,
-            move |gesture, events| {
-                this.on_drawing_area_drag_begin(gesture, events, Some(UniverseCell::Dead))
-            }
+            move |gesture, events| { this.on_drawing_area_drag_begin(gesture, events, Some(0)) }
         ));
 
         right_drag_gesture_controller.connect_update(clone!(
             #[strong(rename_to = this)]
             self,
-            move |gesture, events| {
-                this.on_drawing_area_drag_move(gesture, events, Some(UniverseCell::Dead))
-            }
+            move |gesture, events| { this.on_drawing_area_drag_move(gesture, events, Some(0)) }
         ));
         drawing_area.add_controller(right_drag_gesture_controller);
 
@@ -458,11 +808,44 @@ impl GameOfLifeUniverseGrid {
         match action {
             UniverseGridRequest::Unfreeze => self.set_frozen(false),
             UniverseGridRequest::Redraw(new_universe_state) => {
-                if let Some(new_universe_state) = new_universe_state {
-                    self.imp().universe.replace(Some(new_universe_state));
+                if let Some(mut new_universe_state) = new_universe_state {
+                    self.cancel_skip_forward();
+                    if let Some(universe) = new_universe_state.as_universe_mut() {
+                        universe.set_worker_threads(self.worker_threads() as usize);
+                    }
+                    let previous = self.imp().universe.replace(Some(new_universe_state));
+                    self.record_statistics(previous.as_deref());
+                    self.record_history(previous);
+                    self.sync_automaton();
+                    self.detect_stabilization();
                 }
                 self.redraw();
             }
+            UniverseGridRequest::Skipped(new_universe_state, cancelled) => {
+                // Results of skips that were cancelled or replaced meanwhile are dropped
+                let current = !self.is_running()
+                    && self
+                        .imp()
+                        .skipping
+                        .borrow()
+                        .as_ref()
+                        .is_some_and(|skipping| Arc::ptr_eq(skipping, &cancelled));
+                if current {
+                    self.imp().skipping.take();
+                    self.notify("skipping-forward");
+                    self.process_action(UniverseGridRequest::Redraw(Some(new_universe_state)));
+                }
+            }
+            UniverseGridRequest::Counted(census, request) => {
+                self.imp().counting.set(false);
+                if request == self.imp().census_request.get() {
+                    self.imp().census.replace(census);
+                    self.notify("census");
+                } else {
+                    // The universe changed while counting, only its latest state is worth a census
+                    self.count_objects();
+                }
+            }
         }
 
         glib::ControlFlow::Continue
@@ -474,7 +857,7 @@ impl GameOfLifeUniverseGrid {
         _n_press: i32,
         x: f64,
         y: f64,
-        alter_state: Option<UniverseCell>,
+        alter_state: Option<u8>,
     ) {
         if self.mode() == UniverseGridMode::Unlocked {
             self.imp()
@@ -513,8 +896,9 @@ impl GameOfLifeUniverseGrid {
         &self,
         gesture: &gtk::GestureDrag,
         _events: Option<&gtk::gdk::EventSequence>,
-        alter_state: Option<UniverseCell>,
+        alter_state: Option<u8>,
     ) {
+        self.imp().drag_pan.set((0, 0));
         if self.imp().interaction_state.get() == UniverseGridInteractionState::Ongoing {
             if let Some(point) = gesture.start_point() {
                 self.alter_universe_point(point.0, point.1, alter_state);
@@ -526,13 +910,62 @@ impl GameOfLifeUniverseGrid {
         &self,
         gesture: &gtk::GestureDrag,
         _events: Option<&gtk::gdk::EventSequence>,
-        alter_state: Option<UniverseCell>,
+        alter_state: Option<u8>,
     ) {
         if self.imp().interaction_state.get() == UniverseGridInteractionState::Ongoing {
             if let Some(point) = gesture.offset() {
                 let origin = gesture.start_point().unwrap();
                 self.alter_universe_point(origin.0 + point.0, origin.1 + point.1, alter_state);
             }
+        } else if self.mode() == UniverseGridMode::Locked {
+            if let Some(point) = gesture.offset() {
+                self.on_drawing_area_pan(point.0, point.1);
+            }
+        }
+    }
+
+    /// Moves the viewport of unbounded universes so that the cells follow
+    /// a drag of `x` and `y` pixels from where it started
+    fn on_drawing_area_pan(&self, x: f64, y: f64) {
+        let universe_borrow = self.imp().universe.borrow();
+        let Some(universe) = universe_borrow.as_deref() else {
+            return;
+        };
+        let is_unbounded = universe
+            .as_universe()
+            .is_some_and(|universe| universe.topology().is_unbounded());
+        if !is_unbounded {
+            return;
+        }
+
+        let drawing_area = self.imp().obj();
+        let geometry = GridGeometry::new(
+            universe,
+            drawing_area.width() as f64,
+            drawing_area.height() as f64,
+        );
+        drop(universe_borrow);
+
+        let (rows, columns) = geometry.cells_in_offset(x, y);
+        let (panned_rows, panned_columns) = self.imp().drag_pan.replace((rows, columns));
+        self.pan(panned_rows - rows, panned_columns - columns);
+    }
+
+    /// Moves the viewport of unbounded universes by `rows` and `columns` cells.
+    /// While running, the move is left to the ticking thread
+    pub fn pan(&self, rows: i64, columns: i64) {
+        if self.is_running() {
+            let mut pending_pan = self.imp().pending_pan.lock().unwrap();
+            *pending_pan = (pending_pan.0 + rows, pending_pan.1 + columns);
+        } else if let Some(universe) = self
+            .imp()
+            .universe
+            .borrow_mut()
+            .as_mut()
+            .and_then(|universe| universe.as_universe_mut())
+        {
+            universe.pan(rows, columns);
+            self.redraw();
         }
     }
 
@@ -546,7 +979,7 @@ impl GameOfLifeUniverseGrid {
             .point_under_pointing_device
             .set(widget_area_point_to_universe_cell(
                 &self.imp().obj(),
-                self.imp().universe.borrow().as_ref(),
+                self.imp().universe.borrow().as_deref(),
                 x,
                 y,
             ));
@@ -556,15 +989,15 @@ impl GameOfLifeUniverseGrid {
         self.imp().point_under_pointing_device.set(None);
     }
 
-    /// Alters the universe cell visually located at `x` and `y` coordinates. If `Some(value)`
-    /// is provided it will be used as the new cell value, else the opposite value of the current
-    /// one will be set
-    fn alter_universe_point(&self, x: f64, y: f64, value: Option<UniverseCell>) {
+    /// Alters the universe cell visually located at `x` and `y` coordinates. If `Some(state)`
+    /// is provided it will be used as the new cell state, else empty cells will be set
+    /// to the first non empty state and any other cell will be emptied
+    fn alter_universe_point(&self, x: f64, y: f64, value: Option<u8>) {
         let drawing_area = self.imp().obj();
         let universe_borrow = self.imp().universe.borrow();
 
-        if let Some(universe_point) =
-            widget_area_point_to_universe_cell(&drawing_area, universe_borrow.as_ref(), x, y)
+        if let Some((row, column)) =
+            widget_area_point_to_universe_cell(&drawing_area, universe_borrow.as_deref(), x, y)
         {
             // If a point is found, set its cell value
             drop(universe_borrow);
@@ -574,10 +1007,14 @@ impl GameOfLifeUniverseGrid {
             // NONE value means invert the cell value, SOME value sets it
             let next_value = match value {
                 Some(v) => v,
-                None => !(*universe_point.cell()),
+                None => u8::from(mut_borrow.state(row, column) == 0),
             };
 
-            mut_borrow.set_cell(universe_point.row(), universe_point.column(), next_value);
+            mut_borrow.set_state(row, column, next_value);
+            drop(universe_mut_borrow);
+            self.cancel_skip_forward();
+            self.forget_history();
+            self.record_statistics(None);
             self.redraw();
         }
     }
@@ -621,6 +1058,7 @@ impl GameOfLifeUniverseGrid {
     }
 
     pub fn run(&self) {
+        self.cancel_skip_forward();
         let local_sender = self.get_sender();
 
         let (thread_render_stopper_sender, thread_render_stopper_receiver) =
@@ -634,11 +1072,21 @@ impl GameOfLifeUniverseGrid {
         let thread_universe = self.imp().universe.borrow();
         if let Some(universe) = thread_universe.as_ref() {
             let mut thread_universe = universe.clone();
+            let pending_pan = self.imp().pending_pan.clone();
+            let pending_corpse_decay = self.imp().pending_corpse_decay.clone();
             let wait: u64 = 1000 / u64::from(self.evolution_speed());
             std::thread::spawn(move || {
                 while thread_render_stopper_sender.send(()).is_ok() {
                     std::thread::sleep(std::time::Duration::from_millis(wait));
-                    thread_universe.tick();
+                    let (rows, columns) = std::mem::take(&mut *pending_pan.lock().unwrap());
+                    let corpse_decay = pending_corpse_decay.lock().unwrap().take();
+                    if let Some(universe) = thread_universe.as_universe_mut() {
+                        universe.pan(rows, columns);
+                        if let Some(corpse_decay) = corpse_decay {
+                            universe.set_corpse_decay(corpse_decay);
+                        }
+                    }
+                    thread_universe.step();
                     let _ = local_sender
                         .send_blocking(UniverseGridRequest::Redraw(Some(thread_universe.clone())));
                 }
@@ -653,6 +1101,15 @@ impl GameOfLifeUniverseGrid {
     pub fn halt(&self) {
         let inner = self.imp().render_thread_stopper.take();
         drop(inner);
+
+        // Moves and decays the ticking thread did not get to apply
+        let (rows, columns) = std::mem::take(&mut *self.imp().pending_pan.lock().unwrap());
+        self.pan(rows, columns);
+        let corpse_decay = self.imp().pending_corpse_decay.lock().unwrap().take();
+        if let Some(corpse_decay) = corpse_decay {
+            self.set_corpse_decay(corpse_decay);
+        }
+
         self.notify("running");
     }
 
@@ -664,38 +1121,460 @@ impl GameOfLifeUniverseGrid {
         }
     }
 
-    pub fn get_universe_snapshot(&self) -> UniverseSnapshot {
+    /// Takes a snapshot of the universe, if it can be saved
+    pub fn get_universe_snapshot(&self) -> Option<UniverseSnapshot> {
         let imp = self.imp();
-        imp.universe.borrow().as_ref().unwrap().snapshot()
+        imp.universe
+            .borrow()
+            .as_ref()
+            .and_then(|universe| universe.snapshot())
     }
 
+    /// Replaces the universe with a random soup of the same size. The rule, the
+    /// topology and the decay of dead cells of Life-like universes are kept,
+    /// other automata get the ones of the preferences
     pub fn random_seed(&self) {
+        let settings = &self.imp().settings;
         let current_universe = self.imp().universe.borrow();
-        let (rows, cols) = match current_universe.as_ref() {
-            Some(universe) => (universe.rows(), universe.columns()),
-            None => (200, 200),
-        };
+        let (rows, columns) = current_universe
+            .as_ref()
+            .map_or((200, 200), |universe| (universe.rows(), universe.columns()));
+        let mut new_universe = Universe::new_soup(rows, columns, settings.soup_options());
+        match current_universe
+            .as_ref()
+            .and_then(|universe| universe.as_universe())
+        {
+            Some(universe) => {
+                new_universe.set_rule(*universe.rule());
+                new_universe.set_topology(*universe.topology());
+                new_universe.set_corpse_decay(*universe.corpse_decay());
+            }
+            None => {
+                new_universe.set_rule(settings.universe_rule());
+                new_universe.set_topology(settings.universe_topology());
+                new_universe.set_corpse_decay(settings.corpse_decay());
+            }
+        }
 
         drop(current_universe);
-
-        let new_universe = Universe::new_random(rows, cols);
-        self.process_action(UniverseGridRequest::Redraw(Some(new_universe)));
+        self.process_action(UniverseGridRequest::Redraw(Some(Box::new(new_universe))));
     }
 
     pub fn skip_forward_one(&self) {
-        if let Ok(mut borrow) = self.imp().universe.try_borrow_mut() {
-            if let Some(current_universe) = borrow.as_mut() {
-                current_universe.tick();
-                self.redraw();
+        // Generations recorded after the one shown are brought back rather than computed again
+        if self.seek_history(self.history_position() + 1) {
+            return;
+        }
+
+        let next_universe = self.imp().universe.try_borrow().ok().and_then(|universe| {
+            universe.as_ref().map(|universe| {
+                let mut next_universe = universe.clone();
+                next_universe.step();
+                next_universe
+            })
+        });
+        if let Some(next_universe) = next_universe {
+            self.process_action(UniverseGridRequest::Redraw(Some(next_universe)));
+        }
+    }
+
+    /// Brings the universe back to the generation before the one shown,
+    /// when not running. Returns `false` when there is none in history
+    pub fn step_back(&self) -> bool {
+        let position = self.history_position();
+        position > 0 && self.seek_history(position - 1)
+    }
+
+    /// Shows the generation at `position` in history, when not running.
+    /// Running from there forgets the generations that followed it.
+    /// Returns `false` when there is no generation there, or it is the one shown
+    pub fn seek_history(&self, position: usize) -> bool {
+        if self.is_running() {
+            return false;
+        }
+
+        let sought = self.with_history(|history| {
+            self.imp()
+                .universe
+                .borrow_mut()
+                .as_mut()
+                .is_some_and(|universe| history.seek(universe, position))
+        });
+        if sought {
+            self.cancel_skip_forward();
+            self.sync_automaton();
+            self.notify("population");
+            self.redraw();
+        }
+        sought
+    }
+
+    /// Whether there is a past generation to step back to
+    pub fn can_step_back(&self) -> bool {
+        self.history_position() > 0
+    }
+
+    /// Gets the number of past generations recorded, which is
+    /// also the position of the most recent one in history
+    pub fn history_length(&self) -> usize {
+        self.imp().history.borrow().len()
+    }
+
+    /// Gets the position of the generation shown in history
+    pub fn history_position(&self) -> usize {
+        self.imp().history.borrow().position()
+    }
+
+    /// Gets the generation and the population at each position in history
+    pub fn history_timeline(&self) -> Vec<(u64, usize)> {
+        let universe = self.imp().universe.borrow();
+        match universe.as_deref() {
+            Some(universe) => self.imp().history.borrow().timeline(universe),
+            None => vec![],
+        }
+    }
+
+    /// Sets the number of past generations kept to step back to. Zero keeps none
+    pub fn set_history_depth(&self, value: u32) {
+        self.with_history(|history| history.set_depth(value as usize));
+    }
+
+    /// Calls `f` with the history of the universe shown,
+    /// notifying the history properties it changes
+    fn with_history<R, F: FnOnce(&mut UniverseHistory) -> R>(&self, f: F) -> R {
+        let mut history = self.imp().history.borrow_mut();
+        let (length, position) = (history.len(), history.position());
+        let result = f(&mut history);
+        let (new_length, new_position) = (history.len(), history.position());
+        drop(history);
+
+        if (position > 0) != (new_position > 0) {
+            self.notify("can-step-back");
+        }
+        if length != new_length {
+            self.notify("history-length");
+        }
+        if position != new_position {
+            self.notify("history-position");
+        }
+        result
+    }
+
+    /// Records `previous` in history, when the universe shown was computed
+    /// from it. History is forgotten when the universe was replaced instead
+    fn record_history(&self, previous: Option<Box<dyn CellularAutomaton>>) {
+        let universe = self.imp().universe.borrow();
+        self.with_history(|history| match (previous, universe.as_deref()) {
+            (Some(previous), Some(current)) if current.generation() > previous.generation() => {
+                history.record(previous, current);
             }
+            _ => history.clear(),
+        });
+    }
+
+    /// Forgets the past generations of the universe shown, which
+    /// do not lead to it anymore once its cells are edited
+    fn forget_history(&self) {
+        self.with_history(UniverseHistory::clear);
+        self.imp().stabilization_detector.borrow_mut().reset();
+        self.set_stabilization(None);
+    }
+
+    /// Looks for the Life-like universe shown going through the same states
+    /// over and over, and pauses the first time it does when told to
+    fn detect_stabilization(&self) {
+        let observed = self
+            .imp()
+            .universe
+            .borrow()
+            .as_ref()
+            .and_then(|universe| universe.as_universe())
+            .map(|universe| (universe.generation(), universe.state_hash()));
+        let stabilization = observed.and_then(|(generation, hash)| {
+            self.imp()
+                .stabilization_detector
+                .borrow_mut()
+                .observe(generation, hash)
+        });
+
+        let newly_stabilized = stabilization.is_some() && self.stabilization().is_none();
+        self.set_stabilization(stabilization);
+        if newly_stabilized && self.pauses_on_stabilization() && self.is_running() {
+            self.halt();
+        }
+    }
+
+    /// Records the statistics of the universe shown, `previous` being
+    /// the universe it was computed from, if there is one
+    fn record_statistics(&self, previous: Option<&dyn CellularAutomaton>) {
+        let statistics = self
+            .imp()
+            .universe
+            .borrow()
+            .as_deref()
+            .map(|universe| GenerationStatistics::of(universe, previous));
+        if let Some(statistics) = statistics {
+            self.imp().statistics.borrow_mut().record(statistics);
+            self.notify("population");
+        }
+    }
+
+    /// Gets the statistics of the universe shown
+    pub fn statistics(&self) -> Option<GenerationStatistics> {
+        let universe = self.imp().universe.borrow();
+        let universe = universe.as_deref()?;
+        let recorded = self
+            .imp()
+            .statistics
+            .borrow()
+            .get(universe.generation())
+            .copied();
+        Some(recorded.unwrap_or_else(|| GenerationStatistics::of(universe, None)))
+    }
+
+    /// Gets the generation and the population of, at most, the last
+    /// `count` generations recorded up to the one shown
+    pub fn population_series(&self, count: usize) -> Vec<(u64, usize)> {
+        let generation = self
+            .imp()
+            .universe
+            .borrow()
+            .as_ref()
+            .map_or(0, |universe| universe.generation());
+        self.imp()
+            .statistics
+            .borrow()
+            .population_series(generation, count)
+    }
+
+    /// Writes the statistics of every generation recorded in `format`,
+    /// `None` when there is none
+    pub fn export_statistics(&self, format: StatisticsFormat) -> Option<String> {
+        let statistics = self.imp().statistics.borrow();
+        if statistics.is_empty() {
+            None
+        } else {
+            Some(statistics.export(format))
+        }
+    }
+
+    /// Gets the living cells of the object under the pointing device, in the
+    /// coordinates `motion` takes. `None` when the pointing device is
+    /// not over a living cell of an automaton made of objects
+    pub fn object_under_pointer(&self) -> Option<ObjectCells> {
+        let (row, column) = self.imp().point_under_pointing_device.get()?;
+        self.imp()
+            .universe
+            .borrow()
+            .as_deref()?
+            .as_universe()?
+            .object_at(row, column)
+    }
+
+    /// Finds out how `object`, or the whole universe shown when `None`,
+    /// repeats: its period, and how far it moves over a period
+    pub fn motion(&self, object: Option<&[(i64, i64)]>) -> Result<Motion, MotionError> {
+        self.imp()
+            .universe
+            .borrow()
+            .as_deref()
+            .and_then(|universe| universe.as_universe())
+            .map_or(Err(MotionError::UnsupportedRule), |universe| {
+                universe.motion(object)
+            })
+    }
+
+    /// Gets the census of the objects of the universe shown last taken by
+    /// `take_census`. `None` when it is not made of objects, or its rule
+    /// does not allow one
+    pub fn census(&self) -> Option<Census> {
+        self.imp().census.borrow().clone()
+    }
+
+    /// Takes the census of the objects of the universe shown on another
+    /// thread, notifying `census` once it is done. Only the latest of
+    /// the censuses requested meanwhile is taken
+    pub fn take_census(&self) {
+        let imp = self.imp();
+        imp.census_request
+            .set(imp.census_request.get().wrapping_add(1));
+        if !imp.counting.get() {
+            self.count_objects();
+        }
+    }
+
+    fn count_objects(&self) {
+        let imp = self.imp();
+        let universe = imp.universe.borrow();
+        if let Some(universe) = universe.as_ref() {
+            let thread_universe = universe.clone();
+            let request = imp.census_request.get();
+            let local_sender = self.get_sender();
+            imp.counting.set(true);
+            std::thread::spawn(move || {
+                let census = thread_universe.as_universe().and_then(Census::of);
+                let _ = local_sender.send_blocking(UniverseGridRequest::Counted(census, request));
+            });
+        }
+    }
+
+    /// Gets how the universe shown stabilized, if it did
+    pub fn stabilization(&self) -> Option<Stabilization> {
+        self.imp().stabilization.get()
+    }
+
+    fn set_stabilization(&self, value: Option<Stabilization>) {
+        let was_stabilized = self.imp().stabilization.replace(value).is_some();
+        if was_stabilized != value.is_some() {
+            self.notify("stabilized");
+        }
+    }
+
+    pub fn pauses_on_stabilization(&self) -> bool {
+        self.imp().pauses_on_stabilization.get()
+    }
+
+    /// Sets whether running stops when the universe stops changing or
+    /// starts repeating itself, the first time it does
+    pub fn set_pauses_on_stabilization(&self, value: bool) {
+        self.imp().pauses_on_stabilization.set(value);
+    }
+
+    /// Computes the next `generations` generations of the universe at once,
+    /// on a separate thread, and shows the result when done. Replaces any
+    /// skip forward still being computed. Running, editing or replacing the
+    /// universe meanwhile cancels it, so that its result never overwrites them
+    pub fn skip_forward(&self, generations: u64) {
+        self.cancel_skip_forward();
+
+        let universe = self.imp().universe.borrow();
+        if let Some(universe) = universe.as_ref() {
+            let mut thread_universe = universe.clone();
+            let local_sender = self.get_sender();
+            let cancelled = Arc::new(AtomicBool::new(false));
+            self.imp().skipping.replace(Some(cancelled.clone()));
+            std::thread::spawn(move || {
+                if thread_universe.skip_forward(generations, &cancelled) {
+                    let _ = local_sender
+                        .send_blocking(UniverseGridRequest::Skipped(thread_universe, cancelled));
+                }
+            });
+            drop(universe);
+            self.notify("skipping-forward");
+        }
+    }
+
+    /// Whether a skip forward is being computed
+    pub fn is_skipping_forward(&self) -> bool {
+        self.imp().skipping.borrow().is_some()
+    }
+
+    /// Stops computing the skip forward in progress, if any, leaving the universe as it is
+    pub fn cancel_skip_forward(&self) {
+        if let Some(cancelled) = self.imp().skipping.take() {
+            cancelled.store(true, Ordering::Relaxed);
+            self.notify("skipping-forward");
         }
     }
 
+    /// Calls `f` with the universe being shown, when it is not running, then
+    /// shows what `f` made of it. `f` returns `None` when it could not edit
+    /// the universe, which is then left as it was. Returns what `f` returned
+    pub fn edit_universe<R, F: FnOnce(&mut Universe) -> Option<R>>(&self, f: F) -> Option<R> {
+        if self.is_running() {
+            return None;
+        }
+
+        let result = self
+            .imp()
+            .universe
+            .borrow_mut()
+            .as_mut()
+            .and_then(|universe| universe.as_universe_mut())
+            .and_then(f);
+        if result.is_some() {
+            self.cancel_skip_forward();
+            self.forget_history();
+            self.record_statistics(None);
+            self.sync_automaton();
+            self.redraw();
+        }
+        result
+    }
+
     pub fn set_universe(&self, universe: Universe) {
+        self.set_automaton(Box::new(universe));
+    }
+
+    /// Replaces the universe with any other cellular automaton
+    pub fn set_automaton(&self, mut universe: Box<dyn CellularAutomaton>) {
+        self.cancel_skip_forward();
+        if let Some(universe) = universe.as_universe_mut() {
+            universe.set_worker_threads(self.worker_threads() as usize);
+        }
         self.imp().universe.replace(Some(universe));
+        self.forget_history();
+        self.record_statistics(None);
+        self.sync_automaton();
         self.redraw();
     }
 
+    /// Notifies when the universe shown comes from a different soup
+    /// seed, has a different number of states or is at another generation
+    fn sync_automaton(&self) {
+        let universe = self.imp().universe.borrow();
+        let seed = universe
+            .as_ref()
+            .and_then(|universe| universe.as_universe()?.soup().map(|soup| soup.seed()));
+        let states = universe.as_ref().map_or(2, |universe| universe.states());
+        let generation = universe
+            .as_ref()
+            .map_or(0, |universe| universe.generation());
+        drop(universe);
+
+        if self.imp().seed.replace(seed) != seed {
+            self.notify("seed");
+        }
+        if self.imp().states.replace(states) != states {
+            self.notify("states");
+            self.set_brush_state(self.brush_state());
+        }
+        if self.imp().generation.replace(generation) != generation {
+            self.notify("generation");
+        }
+    }
+
+    /// Gets the state cells are painted with by the primary button
+    pub fn brush_state(&self) -> u8 {
+        self.imp().brush_state.get()
+    }
+
+    /// Sets the state cells are painted with by the primary button. Clamped
+    /// to the states of the universe shown, and never the empty state
+    pub fn set_brush_state(&self, value: u8) {
+        let value = value.clamp(1, self.imp().states.get().max(2) - 1);
+        if self.imp().brush_state.replace(value) != value {
+            self.notify("brush-state");
+        }
+    }
+
+    /// Gets the names of the states cells can be painted with,
+    /// from the first non empty one
+    pub fn state_names(&self) -> Vec<String> {
+        let universe = self.imp().universe.borrow();
+        let Some(universe) = universe.as_ref() else {
+            return vec![];
+        };
+
+        (1..universe.states())
+            .map(|state| {
+                universe
+                    .state_name(state)
+                    .unwrap_or_else(|| format!("{} {}", i18n("State"), state))
+            })
+            .collect()
+    }
+
     pub fn redraw(&self) {
         self.queue_draw();
     }
@@ -753,6 +1632,43 @@ impl GameOfLifeUniverseGrid {
         self.imp().evolution_speed.set(value);
     }
 
+    pub fn worker_threads(&self) -> u32 {
+        self.imp().worker_threads.get()
+    }
+
+    /// Sets the number of threads computing each generation. Zero means one per core.
+    /// Applies to the universe being shown and to the next evolution
+    pub fn set_worker_threads(&self, value: u32) {
+        self.imp().worker_threads.set(value);
+        if let Ok(mut universe) = self.imp().universe.try_borrow_mut() {
+            if let Some(universe) = universe
+                .as_mut()
+                .and_then(|universe| universe.as_universe_mut())
+            {
+                universe.set_worker_threads(value as usize);
+            }
+        }
+    }
+
+    /// Sets how dead cells of the universe being shown fade out.
+    /// While running, the change is left to the ticking thread
+    pub fn set_corpse_decay(&self, value: CorpseDecay) {
+        if self.is_running() {
+            self.imp()
+                .pending_corpse_decay
+                .lock()
+                .unwrap()
+                .replace(value);
+        } else if let Ok(mut universe) = self.imp().universe.try_borrow_mut() {
+            if let Some(universe) = universe
+                .as_mut()
+                .and_then(|universe| universe.as_universe_mut())
+            {
+                universe.set_corpse_decay(value);
+            }
+        }
+    }
+
     pub fn animated(&self) -> bool {
         self.imp().animated.get()
     }
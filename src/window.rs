@@ -1,6 +1,6 @@
 use std::{io::prelude::*, str::FromStr};
 
-use crate::i18n::i18n;
+use crate::i18n::{group_digits, i18n};
 use adw::prelude::AdwApplicationExt;
 use gtk::prelude::*;
 use gtk::subclass::prelude::*;
@@ -8,9 +8,15 @@ use gtk::{gio, glib, glib::clone, CompositeTemplate};
 
 use crate::{
     config::{APPLICATION_G_PATH, G_LOG_DOMAIN},
-    models::{Universe, UniverseGridMode, UniverseSnapshot},
+    models::{
+        MotionError, ResizeAnchor, SnapshotError, SoupOptions, Stabilization, StatisticsFormat,
+        Universe, UniverseGridMode, UniverseRule, UniverseSnapshot, UniverseTopology,
+        UniverseTransform, Wireworld, MOTION_MAX_PERIOD, UNIVERSE_MAX_SIDE,
+    },
     services::{GameOfLifeSettings, Template},
-    widgets::{GameOfLifeNewUniverseView, NewUniverseType},
+    widgets::{
+        speed_label, GameOfLifeNewUniverseView, NewUniverseType, STATISTICS_CHART_GENERATIONS,
+    },
 };
 
 mod imp {
@@ -31,6 +37,21 @@ mod imp {
         #[template_child]
         pub(super) controls: TemplateChild<crate::widgets::GameOfLifeUniverseControls>,
 
+        #[template_child]
+        pub(super) timeline: TemplateChild<crate::widgets::GameOfLifeTimeline>,
+
+        #[template_child]
+        pub(super) census_split_view: TemplateChild<adw::OverlaySplitView>,
+
+        #[template_child]
+        pub(super) census_panel: TemplateChild<crate::widgets::GameOfLifeCensusPanel>,
+
+        #[template_child]
+        pub(super) statistics_split_view: TemplateChild<adw::OverlaySplitView>,
+
+        #[template_child]
+        pub(super) statistics_panel: TemplateChild<crate::widgets::GameOfLifeStatisticsPanel>,
+
         pub(super) mode: std::cell::Cell<UniverseGridMode>,
 
         pub(super) provider: gtk::CssProvider,
@@ -38,6 +59,9 @@ mod imp {
         pub(super) style_manager: adw::StyleManager,
 
         pub(super) settings: GameOfLifeSettings,
+
+        /// The toast shown while a skip forward is being computed
+        pub(super) skip_forward_toast: std::cell::RefCell<Option<adw::Toast>>,
     }
 
     #[glib::object_subclass]
@@ -51,10 +75,16 @@ mod imp {
                 toast_overlay: TemplateChild::default(),
                 universe_grid: TemplateChild::default(),
                 controls: TemplateChild::default(),
+                timeline: TemplateChild::default(),
+                census_split_view: TemplateChild::default(),
+                census_panel: TemplateChild::default(),
+                statistics_split_view: TemplateChild::default(),
+                statistics_panel: TemplateChild::default(),
                 mode: std::cell::Cell::default(),
                 provider: gtk::CssProvider::new(),
                 settings: GameOfLifeSettings::default(),
                 style_manager: adw::StyleManager::default(),
+                skip_forward_toast: std::cell::RefCell::default(),
             }
         }
 
@@ -73,6 +103,52 @@ mod imp {
                 win.skip_forward_one();
             });
 
+            klass.install_action("win.skip-backward-one", None, move |win, _, _| {
+                win.skip_backward_one();
+            });
+
+            klass.install_action("win.skip-forward", None, move |win, _, _| {
+                win.skip_forward_dialog();
+            });
+
+            klass.install_action("win.cancel-skip-forward", None, move |win, _, _| {
+                win.imp().universe_grid.cancel_skip_forward();
+            });
+
+            klass.install_action("win.resize", None, move |win, _, _| {
+                win.resize_dialog();
+            });
+
+            for (action, transform) in [
+                ("win.rotate-clockwise", UniverseTransform::RotateClockwise),
+                ("win.rotate-half-turn", UniverseTransform::RotateHalfTurn),
+                (
+                    "win.rotate-counterclockwise",
+                    UniverseTransform::RotateCounterclockwise,
+                ),
+                ("win.flip-horizontally", UniverseTransform::FlipHorizontally),
+                ("win.flip-vertically", UniverseTransform::FlipVertically),
+                ("win.transpose", UniverseTransform::Transpose),
+            ] {
+                klass.install_action(action, None, move |win, _, _| {
+                    win.transform_universe(|universe| universe.transform(transform));
+                });
+            }
+
+            for (action, rows, columns) in [
+                ("win.shift-up", -1, 0),
+                ("win.shift-down", 1, 0),
+                ("win.shift-left", 0, -1),
+                ("win.shift-right", 0, 1),
+            ] {
+                klass.install_action(action, None, move |win, _, _| {
+                    win.transform_universe(|universe| {
+                        universe.shift(rows, columns);
+                        true
+                    });
+                });
+            }
+
             klass.install_action("win.play", None, move |win, _, _| {
                 win.toggle_run();
             });
@@ -81,6 +157,10 @@ mod imp {
                 win.make_and_save_snapshot();
             });
 
+            klass.install_action("win.export-statistics", None, move |win, _, _| {
+                win.select_and_export_statistics();
+            });
+
             klass.install_action("win.open-snapshot", None, move |win, _, _| {
                 win.select_and_load_snapshot();
             });
@@ -92,6 +172,22 @@ mod imp {
             klass.install_action("win.toggle-instrument-brush", None, move |win, _, _| {
                 win.toggle_instrument_brush();
             });
+
+            klass.install_action("win.toggle-census", None, move |win, _, _| {
+                win.toggle_census();
+            });
+
+            klass.install_action("win.refresh-census", None, move |win, _, _| {
+                win.refresh_census();
+            });
+
+            klass.install_action("win.toggle-statistics", None, move |win, _, _| {
+                win.toggle_statistics();
+            });
+
+            klass.install_action("win.measure-velocity", None, move |win, _, _| {
+                win.measure_velocity();
+            });
         }
 
         fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
@@ -195,8 +291,13 @@ impl GameOfLifeWindow {
         let grid = self.imp().universe_grid.get();
         grid.set_allow_render_on_resize(settings.allow_render_during_resize());
         grid.set_evolution_speed(settings.evolution_speed());
+        grid.set_worker_threads(settings.worker_threads());
+        grid.set_history_depth(settings.history_depth());
+        grid.set_pauses_on_stabilization(settings.pause_on_stabilization());
         grid.set_draw_cells_outline(settings.draw_cells_outline());
         grid.set_fades_dead_cells(settings.fade_out_cells());
+        self.imp().controls.set_palette(&grid.state_names());
+        self.sync_skip_backward_action();
     }
 
     fn setup_provider(&self) {
@@ -222,6 +323,141 @@ impl GameOfLifeWindow {
                     this.notify("run-button-icon-name");
                     this.notify("running");
                     this.notify("stopped");
+                    this.sync_skip_backward_action();
+                    this.sync_census();
+                }
+            ),
+        );
+
+        // Takes the skip forward toast away once skipping is over, however it ended
+        imp.universe_grid.connect_notify_local(
+            Some("skipping-forward"),
+            clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |grid, _| {
+                    if !grid.is_skipping_forward() {
+                        if let Some(toast) = this.imp().skip_forward_toast.take() {
+                            toast.dismiss();
+                        }
+                    }
+                }
+            ),
+        );
+
+        // Steps back are only offered when paused, with a past generation to step back to
+        imp.universe_grid.connect_notify_local(
+            Some("can-step-back"),
+            clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |_, _| {
+                    this.sync_skip_backward_action();
+                }
+            ),
+        );
+
+        // Keeps the timeline along with the generations recorded and the one shown
+        for property in ["generation", "history-length", "history-position"] {
+            imp.universe_grid.connect_notify_local(
+                Some(property),
+                clone!(
+                    #[strong(rename_to = this)]
+                    self,
+                    move |_, _| {
+                        this.sync_timeline();
+                    }
+                ),
+            );
+        }
+
+        // Tells how the universe ended up, the first time it repeats itself
+        imp.universe_grid.connect_notify_local(
+            Some("stabilized"),
+            clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |grid, _| {
+                    if let Some(stabilization) = grid.stabilization() {
+                        this.notify_stabilization(stabilization);
+                    }
+                }
+            ),
+        );
+
+        // Counts objects again for each generation shown while paused
+        imp.universe_grid.connect_notify_local(
+            Some("generation"),
+            clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |_, _| {
+                    this.sync_census();
+                }
+            ),
+        );
+
+        // Shows each census once taken
+        imp.universe_grid.connect_notify_local(
+            Some("census"),
+            clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |grid, _| {
+                    this.imp().census_panel.set_census(grid.census().as_ref());
+                }
+            ),
+        );
+
+        imp.census_split_view.connect_show_sidebar_notify(clone!(
+            #[strong(rename_to = this)]
+            self,
+            move |_| {
+                this.sync_census();
+            }
+        ));
+
+        // Charts each generation as it comes from the running universe
+        imp.universe_grid.connect_notify_local(
+            Some("population"),
+            clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |_, _| {
+                    this.sync_statistics();
+                }
+            ),
+        );
+
+        imp.statistics_split_view
+            .connect_show_sidebar_notify(clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |_| {
+                    this.sync_statistics();
+                }
+            ));
+
+        // Brings back the generation the timeline is dragged to
+        imp.timeline.connect_notify_local(
+            Some("position"),
+            clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |timeline, _| {
+                    this.imp().universe_grid.seek_history(timeline.position());
+                }
+            ),
+        );
+
+        // Offers the states of the automaton shown to be painted with
+        imp.universe_grid.connect_notify_local(
+            Some("states"),
+            clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |grid, _param| {
+                    this.imp().controls.set_palette(&grid.state_names());
                 }
             ),
         );
@@ -256,6 +492,19 @@ impl GameOfLifeWindow {
             ),
         );
 
+        for key in ["corpse-heat", "corpse-decay-rate", "corpse-decay-curve"] {
+            settings.connect_changed(
+                key,
+                clone!(
+                    #[strong(rename_to = this)]
+                    self,
+                    #[strong(rename_to = s)]
+                    settings,
+                    move |_, _| this.imp().universe_grid.set_corpse_decay(s.corpse_decay())
+                ),
+            );
+        }
+
         settings.connect_changed(
             "evolution-speed",
             clone!(
@@ -271,6 +520,51 @@ impl GameOfLifeWindow {
             ),
         );
 
+        settings.connect_changed(
+            "worker-threads",
+            clone!(
+                #[strong(rename_to = this)]
+                self,
+                #[strong(rename_to = s)]
+                settings,
+                move |_, _| {
+                    this.imp()
+                        .universe_grid
+                        .set_worker_threads(s.worker_threads())
+                }
+            ),
+        );
+
+        settings.connect_changed(
+            "history-depth",
+            clone!(
+                #[strong(rename_to = this)]
+                self,
+                #[strong(rename_to = s)]
+                settings,
+                move |_, _| {
+                    this.imp()
+                        .universe_grid
+                        .set_history_depth(s.history_depth())
+                }
+            ),
+        );
+
+        settings.connect_changed(
+            "pause-on-stabilization",
+            clone!(
+                #[strong(rename_to = this)]
+                self,
+                #[strong(rename_to = s)]
+                settings,
+                move |_, _| {
+                    this.imp()
+                        .universe_grid
+                        .set_pauses_on_stabilization(s.pause_on_stabilization())
+                }
+            ),
+        );
+
         settings.connect_changed(
             "allow-render-during-resize",
             clone!(
@@ -370,6 +664,45 @@ impl GameOfLifeWindow {
         controls.set_tools_revealed(!tools_revealed);
     }
 
+    pub fn toggle_census(&self) {
+        let split_view = self.imp().census_split_view.get();
+        split_view.set_show_sidebar(!split_view.shows_sidebar());
+    }
+
+    /// Counts the objects of the universe shown, when the census is shown.
+    /// The panel is updated once the census is taken
+    pub fn refresh_census(&self) {
+        let imp = self.imp();
+        if imp.census_split_view.shows_sidebar() {
+            imp.universe_grid.take_census();
+        }
+    }
+
+    /// Keeps the census along with the universe shown, counting objects
+    /// only while paused, as it would slow evolution down otherwise
+    fn sync_census(&self) {
+        if !self.imp().universe_grid.is_running() {
+            self.refresh_census();
+        }
+    }
+
+    pub fn toggle_statistics(&self) {
+        let split_view = self.imp().statistics_split_view.get();
+        split_view.set_show_sidebar(!split_view.shows_sidebar());
+    }
+
+    /// Shows the statistics of the universe shown, when they are shown
+    fn sync_statistics(&self) {
+        let imp = self.imp();
+        if imp.statistics_split_view.shows_sidebar() {
+            imp.statistics_panel.set_statistics(
+                imp.universe_grid.statistics(),
+                imp.universe_grid
+                    .population_series(STATISTICS_CHART_GENERATIONS),
+            );
+        }
+    }
+
     pub fn toggle_instrument_brush(&self) {
         let settings = &self.imp().settings;
         let controls = self.imp().controls.get();
@@ -421,7 +754,10 @@ impl GameOfLifeWindow {
             move |_, response| {
                 if response == gtk::ResponseType::Accept {
                     if let Some(file) = dialog.file().as_ref() {
-                        let snapshot = win.imp().universe_grid.get_universe_snapshot();
+                        let Some(snapshot) = win.imp().universe_grid.get_universe_snapshot() else {
+                            win.add_toast(i18n("This universe can not be saved"));
+                            return;
+                        };
                         match snapshot.serialize() {
                             Ok(serialized) => {
                                 let file_io_stream =
@@ -470,6 +806,80 @@ impl GameOfLifeWindow {
         dialog.show();
     }
 
+    fn select_and_export_statistics(&self) {
+        if self.imp().universe_grid.statistics().is_none() {
+            self.add_toast(i18n("There are no statistics to export"));
+            return;
+        }
+
+        let csv_filter = gtk::FileFilter::new();
+        csv_filter.set_name(Some(&i18n("CSV files")));
+        csv_filter.add_suffix("csv");
+        csv_filter.add_mime_type("text/csv");
+
+        let json_filter = gtk::FileFilter::new();
+        json_filter.set_name(Some(&i18n("JSON files")));
+        json_filter.add_suffix("json");
+        json_filter.add_mime_type("application/json");
+
+        let dialog = gtk::FileChooserNative::builder()
+            .accept_label(&i18n("_Export"))
+            .cancel_label(&i18n("_Cancel"))
+            .modal(true)
+            .title(&i18n("Export statistics"))
+            .transient_for(self)
+            .select_multiple(false)
+            .action(gtk::FileChooserAction::Save)
+            .build();
+        dialog.add_filter(&csv_filter);
+        dialog.add_filter(&json_filter);
+        dialog.set_current_name("statistics.csv");
+
+        dialog.connect_response(clone!(
+            #[strong]
+            dialog,
+            #[weak(rename_to = win)]
+            self,
+            move |_, response| {
+                if response != gtk::ResponseType::Accept {
+                    return;
+                }
+                let Some(file) = dialog.file() else {
+                    return;
+                };
+
+                // Files are written as JSON when named so, as CSV otherwise
+                let format = file
+                    .path()
+                    .map(|path| StatisticsFormat::from_path(&path))
+                    .unwrap_or_default();
+                let Some(exported) = win.imp().universe_grid.export_statistics(format) else {
+                    win.add_toast(i18n("There are no statistics to export"));
+                    return;
+                };
+
+                let write_result = file.replace_contents(
+                    exported.as_bytes(),
+                    None,
+                    false,
+                    gio::FileCreateFlags::REPLACE_DESTINATION,
+                    gio::Cancellable::NONE,
+                );
+                match write_result {
+                    Ok(_) => {
+                        win.add_toast(i18n("Statistics exported"));
+                    }
+                    Err(error) => {
+                        win.add_toast(i18n("Unable to write to file"));
+                        glib::g_critical!(G_LOG_DOMAIN, "Unable to write to file: {}", error);
+                    }
+                }
+            }
+        ));
+
+        dialog.show();
+    }
+
     fn select_and_load_snapshot(&self) {
         let app = gio::Application::default()
             .expect("Failed to retrieve application singleton")
@@ -516,6 +926,11 @@ impl GameOfLifeWindow {
                                         Ok(snapshot) => {
                                             win.seed_from_snapshot(snapshot);
                                         }
+                                        Err(SnapshotError::UnsupportedVersion) => {
+                                            win.add_toast(i18n(
+                                                "This file was saved by a newer version of Game of Life",
+                                            ));
+                                        }
                                         Err(error) => {
                                             glib::g_critical!(
                                                 G_LOG_DOMAIN,
@@ -566,12 +981,21 @@ impl GameOfLifeWindow {
             move |_, response| {
                 if response == gtk::ResponseType::Ok {
                     let (target_w, target_h) = dialog.size();
+                    let rule = dialog.rule().unwrap_or_default();
+                    let topology = dialog.topology();
                     match dialog.option() {
                         NewUniverseType::Empty => {
-                            win.new_empty(target_w as usize, target_h as usize)
+                            win.new_empty(target_w as usize, target_h as usize, rule, topology)
                         }
-                        NewUniverseType::Random => {
-                            win.new_random(target_w as usize, target_h as usize)
+                        NewUniverseType::Random => win.new_random(
+                            target_w as usize,
+                            target_h as usize,
+                            dialog.soup(),
+                            rule,
+                            topology,
+                        ),
+                        NewUniverseType::Wireworld => {
+                            win.new_wireworld(target_w as usize, target_h as usize)
                         }
                         NewUniverseType::Template(template_name) => {
                             glib::debug!("Seeding from {} template", template_name);
@@ -607,14 +1031,40 @@ impl GameOfLifeWindow {
         dialog.show();
     }
 
-    fn new_empty(&self, rows: usize, columns: usize) {
+    fn new_empty(
+        &self,
+        rows: usize,
+        columns: usize,
+        rule: UniverseRule,
+        topology: UniverseTopology,
+    ) {
         let universe_grid = self.imp().universe_grid.get();
-        universe_grid.set_universe(Universe::new_empty(rows, columns));
+        let mut universe = Universe::new_empty(rows, columns);
+        universe.set_rule(rule);
+        universe.set_topology(topology);
+        universe.set_corpse_decay(self.imp().settings.corpse_decay());
+        universe_grid.set_universe(universe);
     }
 
-    fn new_random(&self, rows: usize, columns: usize) {
+    fn new_random(
+        &self,
+        rows: usize,
+        columns: usize,
+        soup: SoupOptions,
+        rule: UniverseRule,
+        topology: UniverseTopology,
+    ) {
         let universe_grid = self.imp().universe_grid.get();
-        universe_grid.set_universe(Universe::new_random(rows, columns));
+        let mut universe = Universe::new_soup(rows, columns, soup);
+        universe.set_rule(rule);
+        universe.set_topology(topology);
+        universe.set_corpse_decay(self.imp().settings.corpse_decay());
+        universe_grid.set_universe(universe);
+    }
+
+    fn new_wireworld(&self, rows: usize, columns: usize) {
+        let universe_grid = self.imp().universe_grid.get();
+        universe_grid.set_automaton(Box::new(Wireworld::new(rows, columns)));
     }
 
     fn seed_universe(&self) {
@@ -627,10 +1077,231 @@ impl GameOfLifeWindow {
         universe_grid.skip_forward_one();
     }
 
+    fn skip_backward_one(&self) {
+        let universe_grid = self.imp().universe_grid.get();
+        universe_grid.step_back();
+    }
+
+    fn sync_timeline(&self) {
+        let imp = self.imp();
+        let universe_grid = imp.universe_grid.get();
+        imp.timeline.set_visible(universe_grid.history_length() > 0);
+        imp.timeline.set_timeline(
+            universe_grid.history_timeline(),
+            universe_grid.history_position(),
+        );
+    }
+
+    fn sync_skip_backward_action(&self) {
+        let universe_grid = self.imp().universe_grid.get();
+        self.action_set_enabled(
+            "win.skip-backward-one",
+            universe_grid.can_step_back() && !universe_grid.is_running(),
+        );
+    }
+
+    fn skip_forward_dialog(&self) {
+        if self.is_running() {
+            return;
+        }
+
+        let generations_entry = gtk::SpinButton::with_range(1.0, 1e12, 1.0);
+        generations_entry.set_digits(0);
+        generations_entry.set_value(1024.0);
+
+        let dialog = adw::AlertDialog::new(
+            Some(&i18n("Skip Forward")),
+            Some(&i18n(
+                "Two states rules skip ahead quickly on unbounded universes, and on bounded ones whose cells stay away from the edges meanwhile. Other universes compute every generation and may take a while",
+            )),
+        );
+        dialog.add_responses(&[("cancel", &i18n("Cancel")), ("skip", &i18n("Skip"))]);
+        dialog.set_response_appearance("skip", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("skip"));
+        dialog.set_close_response("cancel");
+        dialog.set_extra_child(Some(&generations_entry));
+
+        dialog.connect_response(
+            Some("skip"),
+            clone!(
+                #[weak(rename_to = win)]
+                self,
+                #[strong]
+                generations_entry,
+                move |_, _| {
+                    win.imp()
+                        .universe_grid
+                        .skip_forward(generations_entry.value() as u64);
+                    win.show_skip_forward_toast();
+                }
+            ),
+        );
+        dialog.present(Some(self));
+    }
+
+    /// Shows a toast letting the skip forward being computed be cancelled
+    fn show_skip_forward_toast(&self) {
+        let imp = self.imp();
+        if !imp.universe_grid.is_skipping_forward() {
+            return;
+        }
+
+        let toast = adw::Toast::new(&i18n("Skipping forward…"));
+        toast.set_action_name(Some("win.cancel-skip-forward"));
+        toast.set_button_label(Some(i18n("Cancel").as_str()));
+        toast.set_timeout(0);
+        if let Some(previous) = imp.skip_forward_toast.replace(Some(toast.clone())) {
+            previous.dismiss();
+        }
+        imp.toast_overlay.add_toast(toast);
+    }
+
+    fn resize_dialog(&self) {
+        if self.is_running() {
+            return;
+        }
+
+        let grid = self.imp().universe_grid.get();
+        let rows_entry = gtk::SpinButton::with_range(1.0, UNIVERSE_MAX_SIDE as f64, 1.0);
+        rows_entry.set_value(grid.rows() as f64);
+        let columns_entry = gtk::SpinButton::with_range(1.0, UNIVERSE_MAX_SIDE as f64, 1.0);
+        columns_entry.set_value(grid.columns() as f64);
+        let margin_entry = gtk::SpinButton::with_range(0.0, 100.0, 1.0);
+        margin_entry.set_value(2.0);
+
+        let anchor_labels: Vec<String> = ResizeAnchor::ALL
+            .iter()
+            .map(|anchor| match anchor {
+                ResizeAnchor::TopLeft => i18n("Top left"),
+                ResizeAnchor::Top => i18n("Top"),
+                ResizeAnchor::TopRight => i18n("Top right"),
+                ResizeAnchor::Left => i18n("Left"),
+                ResizeAnchor::Center => i18n("Center"),
+                ResizeAnchor::Right => i18n("Right"),
+                ResizeAnchor::BottomLeft => i18n("Bottom left"),
+                ResizeAnchor::Bottom => i18n("Bottom"),
+                ResizeAnchor::BottomRight => i18n("Bottom right"),
+            })
+            .collect();
+        let anchor_labels: Vec<&str> = anchor_labels.iter().map(String::as_str).collect();
+        let anchor_dropdown = gtk::DropDown::from_strings(&anchor_labels);
+        let center_index = ResizeAnchor::ALL
+            .iter()
+            .position(|anchor| *anchor == ResizeAnchor::Center)
+            .unwrap_or_default();
+        anchor_dropdown.set_selected(center_index as u32);
+
+        let fields = gtk::Grid::builder()
+            .row_spacing(6)
+            .column_spacing(12)
+            .build();
+        for (row, (label, field)) in [
+            (i18n("Rows"), rows_entry.upcast_ref::<gtk::Widget>()),
+            (i18n("Columns"), columns_entry.upcast_ref()),
+            (i18n("Anchor"), anchor_dropdown.upcast_ref()),
+            (i18n("Crop margin"), margin_entry.upcast_ref()),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let label = gtk::Label::builder().label(label).xalign(0.0).build();
+            fields.attach(&label, 0, row as i32, 1, 1);
+            fields.attach(field, 1, row as i32, 1, 1);
+        }
+
+        let dialog = adw::AlertDialog::new(
+            Some(&i18n("Resize Universe")),
+            Some(&i18n(
+                "Rows and columns are added or removed on the sides away from the anchor. Cropping fits the universe around its living cells, leaving a margin of dead ones",
+            )),
+        );
+        dialog.add_responses(&[
+            ("cancel", &i18n("Cancel")),
+            ("crop", &i18n("Crop to Living Cells")),
+            ("resize", &i18n("Resize")),
+        ]);
+        dialog.set_response_appearance("resize", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("resize"));
+        dialog.set_close_response("cancel");
+        dialog.set_extra_child(Some(&fields));
+
+        dialog.connect_response(
+            Some("resize"),
+            clone!(
+                #[weak(rename_to = win)]
+                self,
+                #[strong]
+                rows_entry,
+                #[strong]
+                columns_entry,
+                #[strong]
+                anchor_dropdown,
+                move |_, _| {
+                    let anchor = ResizeAnchor::ALL
+                        .get(anchor_dropdown.selected() as usize)
+                        .copied()
+                        .unwrap_or_default();
+                    let (rows, columns) = (rows_entry.value(), columns_entry.value());
+                    let resized = win.imp().universe_grid.edit_universe(|universe| {
+                        universe.resize(rows as usize, columns as usize, anchor);
+                        Some(())
+                    });
+                    if resized.is_none() {
+                        win.add_toast(i18n("This universe can not be resized"));
+                    }
+                }
+            ),
+        );
+
+        dialog.connect_response(
+            Some("crop"),
+            clone!(
+                #[weak(rename_to = win)]
+                self,
+                #[strong]
+                margin_entry,
+                move |_, _| {
+                    let margin = margin_entry.value() as usize;
+                    match win
+                        .imp()
+                        .universe_grid
+                        .edit_universe(|universe| Some(universe.crop_to_content(margin)))
+                    {
+                        Some(true) => (),
+                        Some(false) => win.add_toast(i18n("There are no living cells to crop to")),
+                        None => win.add_toast(i18n("This universe can not be resized")),
+                    }
+                }
+            ),
+        );
+        dialog.present(Some(self));
+    }
+
+    /// Rotates, flips or shifts the universe shown, as long as it is paused
+    fn transform_universe<F: FnOnce(&mut Universe) -> bool>(&self, f: F) {
+        if self.is_running() {
+            return;
+        }
+
+        let transformed = self
+            .imp()
+            .universe_grid
+            .edit_universe(|universe| f(universe).then_some(()));
+        if transformed.is_none() {
+            self.add_toast(i18n(
+                "This universe can not be transformed this way without changing the neighbours of its cells",
+            ));
+        }
+    }
+
     fn seed_from_snapshot(&self, snapshot: UniverseSnapshot) {
         let universe_grid = self.imp().universe_grid.get();
-        let universe = snapshot.into();
-        universe_grid.set_universe(universe);
+        // Snapshots taken before dead cells decay could be configured follow the preferences
+        let follows_preferences = snapshot.corpse_decay().is_none();
+        universe_grid.set_automaton(snapshot.into());
+        if follows_preferences {
+            universe_grid.set_corpse_decay(self.imp().settings.corpse_decay());
+        }
     }
 
     fn update_widgets(&self) {
@@ -656,6 +1327,75 @@ impl GameOfLifeWindow {
         self.set_default_size(settings.window_width(), settings.window_height());
     }
 
+    /// Tells how the object under the pointing device, or the whole
+    /// universe when it is not over a living cell, repeats and moves
+    fn measure_velocity(&self) {
+        let grid = &self.imp().universe_grid;
+        let object = grid.object_under_pointer();
+        let is_object = object.is_some();
+        let msg = match grid.motion(object.as_deref()) {
+            Ok(motion) if motion.is_moving() => {
+                let (rows, columns) = motion.displacement();
+                let msg = if is_object {
+                    i18n("This object moves at {speed}: ({rows}, {columns}) cells every {period} generations")
+                } else {
+                    i18n("This universe moves at {speed}: ({rows}, {columns}) cells every {period} generations")
+                };
+                msg.replace("{speed}", &speed_label(&motion))
+                    .replace("{rows}", &format!("{rows:+}"))
+                    .replace("{columns}", &format!("{columns:+}"))
+                    .replace("{period}", &group_digits(motion.period()))
+            }
+            Ok(motion) if motion.period() == 1 => {
+                if is_object {
+                    i18n("This object is a still life")
+                } else {
+                    i18n("This universe does not change")
+                }
+            }
+            Ok(motion) => {
+                let msg = if is_object {
+                    i18n("This object oscillates with period {period}")
+                } else {
+                    i18n("This universe oscillates with period {period}")
+                };
+                msg.replace("{period}", &group_digits(motion.period()))
+            }
+            Err(MotionError::DiedOut) => {
+                if is_object {
+                    i18n("This object dies out")
+                } else {
+                    i18n("This universe dies out")
+                }
+            }
+            Err(MotionError::NoPeriod) => {
+                let msg = if is_object {
+                    i18n("This object does not repeat within {generations} generations")
+                } else {
+                    i18n("This universe does not repeat within {generations} generations")
+                };
+                msg.replace("{generations}", &group_digits(MOTION_MAX_PERIOD))
+            }
+            Err(MotionError::UnsupportedRule) => i18n(
+                "Velocity can only be measured in universes following a two states Life-like rule",
+            ),
+        };
+        self.add_toast(msg);
+    }
+
+    fn notify_stabilization(&self, stabilization: Stabilization) {
+        let generation = group_digits(stabilization.generation());
+        let msg = if stabilization.is_static() {
+            i18n("Stabilized as a still life at generation {generation}")
+                .replace("{generation}", &generation)
+        } else {
+            i18n("Stabilized with period {period} at generation {generation}")
+                .replace("{period}", &group_digits(stabilization.period()))
+                .replace("{generation}", &generation)
+        };
+        self.add_toast(msg);
+    }
+
     fn add_toast(&self, msg: String) {
         let toast = adw::Toast::new(&msg);
         self.imp().toast_overlay.add_toast(toast);